@@ -0,0 +1,54 @@
+use aoc::fetch::InputCache;
+use aoc::{days, DayEntry};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::fs;
+use std::path::Path;
+
+/// Load the real input of a day if it exists, otherwise fall back to the example input
+fn load_input(day: &DayEntry) -> String {
+    let example = Path::new("src")
+        .join(day.dir())
+        .join("examples")
+        .join("example.txt");
+    fs::read_to_string(InputCache::new("inputs").path(day.year, day.number))
+        .or_else(|_| fs::read_to_string(example))
+        .expect("failed to read input")
+}
+
+fn bench_day(c: &mut Criterion, day: &DayEntry) {
+    let input = load_input(day);
+    let parsed = (day.parse)(&input).expect("failed to parse input");
+
+    let mut group = c.benchmark_group(format!("{}/{}", day.year, day.name));
+    group.bench_function("parse", |b| b.iter(|| (day.parse)(black_box(&input))));
+    group.bench_function("part1", |b| b.iter(|| (day.part1)(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| (day.part2)(black_box(&parsed))));
+    group.finish();
+}
+
+/// Solve generated inputs of increasing size, to see how a day scales
+fn bench_scaling(c: &mut Criterion, day: &DayEntry) {
+    let mut group = c.benchmark_group(format!("{}/{}/generated", day.year, day.name));
+    for size in [100, 1000] {
+        let input = day.generate(0, size);
+        group.bench_with_input(BenchmarkId::new("run", size), &input, |b, input| {
+            b.iter(|| day.run(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_all(c: &mut Criterion) {
+    for day in days() {
+        bench_day(c, day);
+    }
+}
+
+fn bench_all_scaling(c: &mut Criterion) {
+    for day in days() {
+        bench_scaling(c, day);
+    }
+}
+
+criterion_group!(benches, bench_all, bench_all_scaling);
+criterion_main!(benches);
@@ -0,0 +1,131 @@
+//! Generates a test for each part of every example in `src/yearYYYY/dayNN/examples`
+//!
+//! An example is a `NAME.txt` input, with the answers to it in `NAME.toml` next to it. A part
+//! without an answer still gets a test, but an ignored one, so that it shows up in the test output.
+//! So does a part that is still the `todo!()` of the template.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn sorted_dir(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries.map(|entry| entry.unwrap().path()).collect(),
+        Err(_) => vec![],
+    };
+    paths.sort();
+    paths
+}
+
+/// Turn the name of an example into something that can be part of a function name
+fn identifier(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match name.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("_{}", name),
+        false => name,
+    }
+}
+
+fn has_answer(answers: Option<&toml::Value>, part: u32) -> bool {
+    answers
+        .and_then(|answers| answers.get(format!("part{}", part)))
+        .is_some()
+}
+
+/// The subdirectories of `dir` named `prefix` followed by a number, with that number
+fn numbered(dir: &Path, prefix: &str) -> Vec<(u32, String, PathBuf)> {
+    sorted_dir(dir)
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let number = name.strip_prefix(prefix)?.parse().ok()?;
+            Some((number, name, path))
+        })
+        .collect()
+}
+
+fn main() {
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    println!("cargo:rerun-if-changed=src");
+
+    let mut tests = String::new();
+    for (year, year_name, year_dir) in numbered(&src, "year") {
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+        writeln!(tests, "mod {} {{\n    use super::*;", year_name).unwrap();
+        for (day, name, dir) in numbered(&year_dir, "day") {
+            write_day(&mut tests, year, day, &name, &dir);
+        }
+        writeln!(tests, "}}").unwrap();
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
+
+/// Write the tests of the examples of one day
+fn write_day(tests: &mut String, year: u32, day: u32, name: &str, dir: &Path) {
+    let examples = dir.join("examples");
+    println!("cargo:rerun-if-changed={}", examples.display());
+
+    // the template's parts are todo!("impl part N of day {}", DAY) until they are written
+    let source = fs::read_to_string(dir.join("mod.rs")).unwrap_or_default();
+    let todo = |part| source.contains(&format!("todo!(\"impl part {}", part));
+
+    writeln!(tests, "mod {} {{\n    use super::*;", name).unwrap();
+    for input in sorted_dir(&examples) {
+        if input.extension() != Some("txt".as_ref()) {
+            continue;
+        }
+        let example = identifier(input.file_stem().unwrap().to_str().unwrap());
+        let sidecar = input.with_extension("toml");
+        let answers: Option<toml::Value> = fs::read_to_string(&sidecar).ok().map(|text| {
+            text.parse()
+                .unwrap_or_else(|e| panic!("{}: {}", sidecar.display(), e))
+        });
+
+        for part in 1..=2 {
+            if has_answer(answers.as_ref(), part) && todo(part) {
+                writeln!(
+                    tests,
+                    "    example_test!(#[ignore = \"part {} of {} is not implemented yet\"] \
+                     {}_part{}: {}, {}, {}, {:?}, {:?});",
+                    part, name, example, part, year, day, part, input, sidecar,
+                )
+                .unwrap();
+            } else if has_answer(answers.as_ref(), part) {
+                writeln!(
+                    tests,
+                    "    example_test!({}_part{}: {}, {}, {}, {:?}, {:?});",
+                    example, part, year, day, part, input, sidecar,
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    tests,
+                    "    example_test!(#[ignore = \"no answer to part {} in {}\"] \
+                     {}_part{}: {}, {}, {}, {:?});",
+                    part,
+                    sidecar.file_name().unwrap().to_str().unwrap(),
+                    example,
+                    part,
+                    year,
+                    day,
+                    part,
+                    input,
+                )
+                .unwrap();
+            }
+        }
+    }
+    writeln!(tests, "}}").unwrap();
+}
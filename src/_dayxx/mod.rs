@@ -1,36 +1,44 @@
-pub fn parse(input: &str) -> () {
-    todo!("impl parse")
-}
+use crate::answer::Answer;
+use crate::error::{ParseError, SolveError};
+use crate::solution::{Generator, Solution};
+use fastrand::Rng;
+
+const DAY: u32 = 0;
+
+pub struct DayXX;
+solution!(DayXX);
+
+impl Solution for DayXX {
+    type Input = ();
+
+    fn parse(_input: &str) -> Result<(), ParseError> {
+        // nothing to parse into until there is an Input
+        Ok(())
+    }
+
+    fn part1(_data: &()) -> Result<Answer, SolveError> {
+        todo!("impl part 1 of day {}", DAY)
+    }
 
-pub fn part1(input: &str) -> usize {
-    let data = parse(input);
-    todo!("impl part 1")
+    fn part2(_data: &()) -> Result<Answer, SolveError> {
+        todo!("impl part 2 of day {}", DAY)
+    }
 }
 
-pub fn part2(input: &str) -> usize {
-    let data = parse(input);
-    todo!("impl part 2")
+/// `size` is the number of ...
+impl Generator for DayXX {
+    fn generate(_rng: &mut Rng, _size: usize) -> String {
+        todo!("impl generate")
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, part2};
+    use super::parse;
 
     #[test]
     pub fn test_parse() {
-        let input = include_str!("test-input");
-        assert_eq!(parse(input), ());
-    }
-
-    #[test]
-    pub fn test_part1() {
-        let input = include_str!("test-input");
-        assert_eq!(part1(input), 42);
-    }
-
-    #[test]
-    pub fn test_part2() {
-        let input = include_str!("test-input");
-        assert_eq!(part2(input), 1337);
+        let input = include_str!("examples/example.txt");
+        assert_eq!(parse(input), Ok(()));
     }
 }
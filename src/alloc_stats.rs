@@ -0,0 +1,183 @@
+//! Counting the heap allocations made by the code running on a thread
+//!
+//! [Counting] has to be the `#[global_allocator]` of the program, and only counts once it has
+//! been [enable]d. Until then it costs no more than an atomic load on top of the system
+//! allocator. The counters are per thread, so other threads allocating at the same time don't
+//! get in the way, but memory freed by another thread than the one that allocated it does.
+
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The system allocator, counting the allocations of each thread
+pub struct Counting;
+
+/// The allocations made while running something
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// Number of allocations, where growing or shrinking one counts as another
+    pub allocations: u64,
+
+    /// Total size of every allocation
+    pub bytes: u64,
+
+    /// The most heap that was in use at once, not counting what was in use before
+    pub peak: u64,
+}
+
+/// A number of bytes, shown in the largest unit that keeps it above 1
+pub struct Bytes(pub u64);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+
+    /// Can go below zero when freeing memory that was allocated before counting started
+    live: i64,
+    peak: i64,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters::ZERO) };
+}
+
+impl Counters {
+    const ZERO: Counters = Counters {
+        allocations: 0,
+        bytes: 0,
+        live: 0,
+        peak: 0,
+    };
+
+    fn resize(&mut self, old_size: usize, new_size: usize) {
+        self.allocations += 1;
+        self.bytes += new_size as u64;
+        self.live += new_size as i64 - old_size as i64;
+        self.peak = self.peak.max(self.live);
+    }
+}
+
+fn count(update: impl FnOnce(&mut Counters)) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    // the thread local is gone while a thread shuts down, and then there is nobody to tell
+    let _ = COUNTERS.try_with(|cell| {
+        let mut counters = cell.get();
+        update(&mut counters);
+        cell.set(counters);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count(|counters| counters.resize(0, layout.size()));
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(|counters| counters.resize(0, layout.size()));
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        count(|counters| counters.live -= layout.size() as i64);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            count(|counters| counters.resize(layout.size(), new_size));
+        }
+        new_ptr
+    }
+}
+
+/// Start counting allocations
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Run `f` and count the allocations it makes on this thread
+///
+/// `None` unless counting has been [enable]d.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    COUNTERS.with(|cell| cell.set(Counters::ZERO));
+    let out = f();
+    let counters = COUNTERS.with(Cell::get);
+
+    let stats = ENABLED.load(Ordering::Relaxed).then(|| AllocStats {
+        allocations: counters.allocations,
+        bytes: counters.bytes,
+        peak: counters.peak.max(0) as u64,
+    });
+    (out, stats)
+}
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let bytes = self.0 as f64;
+        let text = match bytes {
+            n if n < 1024.0 => format!("{}B", self.0),
+            n if n < 1024.0 * 1024.0 => format!("{:.1}KiB", n / 1024.0),
+            n if n < 1024.0 * 1024.0 * 1024.0 => format!("{:.1}MiB", n / 1024.0 / 1024.0),
+            n => format!("{:.1}GiB", n / 1024.0 / 1024.0 / 1024.0),
+        };
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{enable, measure, Bytes, Counting};
+    use std::hint::black_box;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    pub fn test_measure() {
+        enable();
+
+        let (_, stats) = measure(|| black_box(vec![0u8; 1000]));
+        let stats = stats.unwrap();
+        assert_eq!(
+            (stats.allocations, stats.bytes, stats.peak),
+            (1, 1000, 1000)
+        );
+
+        let (_, stats) = measure(|| {
+            let mut grown = Vec::with_capacity(100);
+            grown.extend_from_slice(&[1u8; 100]);
+            grown.reserve_exact(200);
+            black_box(&grown);
+            drop(grown);
+            black_box(vec![0u8; 50]);
+        });
+        let stats = stats.unwrap();
+        assert_eq!((stats.allocations, stats.bytes, stats.peak), (3, 450, 300));
+
+        let (_, stats) = measure(|| black_box(1 + 1));
+        assert_eq!(stats.unwrap().allocations, 0);
+    }
+
+    #[test]
+    pub fn test_bytes() {
+        assert_eq!(Bytes(512).to_string(), "512B");
+        assert_eq!(Bytes(1536).to_string(), "1.5KiB");
+        assert_eq!(format!("{:>8}", Bytes(2 * 1024 * 1024)), "  2.0MiB");
+    }
+}
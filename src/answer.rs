@@ -0,0 +1,267 @@
+use num_bigint::BigInt;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt::{self, Display, Formatter};
+
+/// The answer to one part of a puzzle
+///
+/// Numeric answers compare equal by value regardless of variant, so `Signed(5)` equals
+/// `Unsigned(5)`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(BigInt),
+
+    /// Answers that aren't numbers, e.g. letters rendered by the puzzle
+    Text(String),
+}
+
+impl Answer {
+    /// Read an answer as it is written, as a number if it looks like one
+    pub fn parse(s: &str) -> Answer {
+        if let Ok(n) = s.parse() {
+            Answer::Signed(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::Big(n)
+        } else {
+            Answer::Text(s.to_string())
+        }
+    }
+
+    /// Get the value of a numeric answer as a [BigInt]
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Answer::Signed(n) => Some(BigInt::from(*n)),
+            Answer::Unsigned(n) => Some(BigInt::from(*n)),
+            Answer::Big(n) => Some(n.clone()),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => n.fmt(f),
+            Answer::Unsigned(n) => n.fmt(f),
+            Answer::Big(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        use Answer::*;
+        match (self, other) {
+            (Signed(a), Signed(b)) => a == b,
+            (Unsigned(a), Unsigned(b)) => a == b,
+            (Signed(a), Unsigned(b)) | (Unsigned(b), Signed(a)) => *a >= 0 && *a as u64 == *b,
+            (Text(a), Text(b)) => a == b,
+            (Text(_), _) | (_, Text(_)) => false,
+            (a, b) => a.to_bigint() == b.to_bigint(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+macro_rules! impl_from {
+    ($variant:ident($inner:ty): $($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::$variant(n as $inner)
+            }
+        })*
+    };
+}
+
+impl_from!(Signed(i64): i8, i16, i32, i64, isize);
+impl_from!(Unsigned(u64): u8, u16, u32, u64, usize);
+
+/// Shrunk to [Answer::Signed] or [Answer::Unsigned] if it fits, so it is written as a number
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        if let Ok(n) = i64::try_from(&n) {
+            Answer::Signed(n)
+        } else if let Ok(n) = u64::try_from(&n) {
+            Answer::Unsigned(n)
+        } else {
+            Answer::Big(n)
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        BigInt::from(n).into()
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        BigInt::from(n).into()
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Big integers that don't fit in 64 bits are serialized as strings, since most formats can't
+/// hold them as numbers.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Signed(n) => serializer.serialize_i64(*n),
+            Answer::Unsigned(n) => serializer.serialize_u64(*n),
+            Answer::Big(n) => match Answer::from(n.clone()) {
+                Answer::Big(n) => serializer.serialize_str(&n.to_string()),
+                small => small.serialize(serializer),
+            },
+            Answer::Text(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+/// Strings are deserialized as [Answer::Text], except those written by [Answer::Big]: integers in
+/// plain decimal that don't fit in 64 bits. So `"42"` and `"0123"` stay text.
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+
+        impl<'de> Visitor<'de> for AnswerVisitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "an integer or a string")
+            }
+
+            fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+                Ok(Answer::Signed(n))
+            }
+
+            fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+                Ok(Answer::Unsigned(n))
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+                Ok(match s.parse::<BigInt>() {
+                    Ok(n) if n.to_string() == s => match Answer::from(n) {
+                        Answer::Big(n) => Answer::Big(n),
+                        _ => Answer::Text(s.to_string()),
+                    },
+                    _ => Answer::Text(s.to_string()),
+                })
+            }
+        }
+
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+    use num_bigint::BigInt;
+
+    #[test]
+    pub fn test_eq() {
+        assert_eq!(Answer::from(5i32), Answer::from(5usize));
+        assert_ne!(Answer::from(-5i32), Answer::from(5usize));
+        assert_eq!(Answer::from(BigInt::from(1337)), Answer::from(1337u64));
+        assert_ne!(Answer::from("1337"), Answer::from(1337u64));
+    }
+
+    #[test]
+    pub fn test_from_wide() {
+        assert!(matches!(Answer::from(BigInt::from(-3)), Answer::Signed(-3)));
+        assert!(matches!(
+            Answer::from(u64::MAX as u128),
+            Answer::Unsigned(u64::MAX)
+        ));
+        let big = Answer::from(u128::MAX);
+        assert_eq!(big.to_string(), "340282366920938463463374607431768211455");
+        assert!(matches!(big, Answer::Big(_)));
+    }
+
+    #[test]
+    pub fn test_display() {
+        assert_eq!(Answer::from(-42i64).to_string(), "-42");
+        assert_eq!(Answer::from("EFGHJKLZ").to_string(), "EFGHJKLZ");
+    }
+
+    #[test]
+    pub fn test_parse() {
+        assert!(matches!(Answer::parse("-7"), Answer::Signed(-7)));
+        assert!(matches!(
+            Answer::parse("18446744073709551615"),
+            Answer::Unsigned(u64::MAX)
+        ));
+        assert!(matches!(
+            Answer::parse("123456789012345678901234567890"),
+            Answer::Big(_)
+        ));
+        assert_eq!(Answer::parse("EFGHJKLZ"), Answer::from("EFGHJKLZ"));
+    }
+
+    #[test]
+    pub fn test_serde() {
+        let big: BigInt = "123456789012345678901234567890".parse().unwrap();
+        let answers = vec![
+            Answer::from(-1i64),
+            Answer::from(2u64),
+            Answer::from(big),
+            Answer::from("ABC"),
+        ];
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"[-1,2,"123456789012345678901234567890","ABC"]"#);
+
+        let parsed: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, answers);
+    }
+
+    #[test]
+    pub fn test_serde_text_numbers() {
+        let answers = vec![
+            Answer::from("42"),
+            Answer::from("0123"),
+            Answer::from("-0"),
+            Answer::Big(BigInt::from(7)),
+        ];
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"["42","0123","-0",7]"#);
+
+        let parsed: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, answers);
+        assert!(parsed[..3]
+            .iter()
+            .all(|answer| matches!(answer, Answer::Text(_))));
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Parts {
+            part1: Answer,
+        }
+        let text = toml::to_string(&Parts {
+            part1: Answer::from("42"),
+        })
+        .unwrap();
+        assert_eq!(
+            toml::from_str::<Parts>(&text).unwrap().part1,
+            Answer::from("42")
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Timing the days repeatedly, and comparing the results against a saved baseline
+
+use crate::registry::{DayEntry, Parsed};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How many times to run each stage of a day
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Take this many samples, unless `max_time` runs out first
+    pub samples: usize,
+
+    /// Stop sampling a stage after this long. At least one sample is always taken.
+    pub max_time: Duration,
+}
+
+/// Summary of the samples of one stage, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayBench {
+    pub day: u32,
+    pub name: String,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// The results of a benchmark run, as written to and read from JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub year: u32,
+    pub days: Vec<DayBench>,
+}
+
+/// The change in median time of one stage compared to the baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub name: String,
+    pub stage: &'static str,
+    pub baseline: f64,
+    pub current: f64,
+
+    /// Relative change, e.g. `0.1` for 10% slower
+    pub change: f64,
+
+    /// Whether the change is beyond the threshold given to [compare]
+    pub regression: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            samples: 100,
+            max_time: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Stats {
+    /// Summarize some samples. Panics if there are none.
+    pub fn new(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let n = nanos.len();
+        let median = if n.is_multiple_of(2) {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2.0
+        } else {
+            nanos[n / 2]
+        };
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Stats {
+            samples: n,
+            min: nanos[0],
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// Call `f` repeatedly and collect how long each call took
+fn sample<T>(options: BenchOptions, mut f: impl FnMut() -> T) -> Stats {
+    let start = Instant::now();
+    let mut samples = Vec::with_capacity(options.samples);
+
+    // warm up caches and the branch predictor before measuring
+    black_box(f());
+
+    while samples.is_empty()
+        || (samples.len() < options.samples && start.elapsed() < options.max_time)
+    {
+        let before = Instant::now();
+        black_box(f());
+        samples.push(before.elapsed());
+    }
+
+    Stats::new(&samples)
+}
+
+/// Time the parse, part 1 and part 2 of a day separately
+///
+/// `on_stage` is called with the name of each stage before it starts. Fails without timing
+/// anything if the input doesn't parse or a part has no answer for it.
+pub fn bench_day(
+    day: &DayEntry,
+    input: &str,
+    options: BenchOptions,
+    mut on_stage: impl FnMut(&str),
+) -> Result<DayBench, Box<dyn Error>> {
+    let parsed: Parsed = (day.parse)(input)?;
+    (day.part1)(&parsed)?;
+    (day.part2)(&parsed)?;
+
+    on_stage("parse");
+    let parse = sample(options, || (day.parse)(black_box(input)));
+    on_stage("part1");
+    let part1 = sample(options, || (day.part1)(black_box(&parsed)));
+    on_stage("part2");
+    let part2 = sample(options, || (day.part2)(black_box(&parsed)));
+
+    Ok(DayBench {
+        day: day.number,
+        name: day.name.to_string(),
+        parse,
+        part1,
+        part2,
+    })
+}
+
+/// Compare the median times of every stage present in both reports
+///
+/// A stage is a regression if it got slower by more than `threshold`, e.g. `0.1` for 10%.
+pub fn compare(baseline: &BenchReport, current: &BenchReport, threshold: f64) -> Vec<Comparison> {
+    let mut comparisons = vec![];
+    for day in &current.days {
+        let before = match baseline.days.iter().find(|b| b.day == day.day) {
+            Some(before) => before,
+            None => continue,
+        };
+
+        let stages = [
+            ("parse", before.parse, day.parse),
+            ("part1", before.part1, day.part1),
+            ("part2", before.part2, day.part2),
+        ];
+        for (stage, before, after) in stages {
+            let change = after.median / before.median - 1.0;
+            comparisons.push(Comparison {
+                name: day.name.clone(),
+                stage,
+                baseline: before.median,
+                current: after.median,
+                change,
+                regression: change > threshold,
+            });
+        }
+    }
+    comparisons
+}
+
+/// Formats a number of nanoseconds with a fitting unit
+pub struct Nanos(pub f64);
+
+impl Display for Nanos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (value, unit) = match self.0 {
+            n if n < 1e3 => (n, "ns"),
+            n if n < 1e6 => (n / 1e3, "µs"),
+            n if n < 1e9 => (n / 1e6, "ms"),
+            n => (n / 1e9, "s"),
+        };
+        f.pad(&format!("{:.2}{}", value, unit))
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>9}  median {:>9}  mean {:>9}  stddev {:>9}",
+            Nanos(self.min),
+            Nanos(self.median),
+            Nanos(self.mean),
+            Nanos(self.stddev)
+        )
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {} -> {} ({:+.1}%){}",
+            self.name,
+            self.stage,
+            Nanos(self.baseline),
+            Nanos(self.current),
+            self.change * 100.0,
+            if self.regression { "  REGRESSION" } else { "" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, BenchReport, DayBench, Nanos, Stats};
+    use std::time::Duration;
+
+    fn stats(median: f64) -> Stats {
+        Stats {
+            samples: 1,
+            min: median,
+            median,
+            mean: median,
+            stddev: 0.0,
+        }
+    }
+
+    fn report(medians: &[(u32, f64)]) -> BenchReport {
+        BenchReport {
+            year: 2021,
+            days: medians
+                .iter()
+                .map(|&(day, median)| DayBench {
+                    day,
+                    name: format!("day{:02}", day),
+                    parse: stats(median),
+                    part1: stats(median),
+                    part2: stats(median),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    pub fn test_stats() {
+        let samples: Vec<Duration> = [4, 2, 8, 6]
+            .iter()
+            .map(|&n| Duration::from_nanos(n))
+            .collect();
+        let stats = Stats::new(&samples);
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.median, 5.0);
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.stddev, 5f64.sqrt());
+    }
+
+    #[test]
+    pub fn test_compare() {
+        let baseline = report(&[(1, 100.0), (2, 100.0)]);
+        let current = report(&[(1, 105.0), (2, 150.0), (3, 100.0)]);
+
+        let comparisons = compare(&baseline, &current, 0.1);
+        assert_eq!(comparisons.len(), 6);
+        assert!(comparisons[..3].iter().all(|c| !c.regression));
+        assert!(comparisons[3..].iter().all(|c| c.regression));
+        assert!((comparisons[3].change - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn test_json() {
+        let report = report(&[(1, 100.0)]);
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<BenchReport>(&json).unwrap(), report);
+    }
+
+    #[test]
+    pub fn test_nanos() {
+        assert_eq!(Nanos(12.0).to_string(), "12.00ns");
+        assert_eq!(Nanos(1500.0).to_string(), "1.50µs");
+        assert_eq!(Nanos(2.5e9).to_string(), "2.50s");
+    }
+}
@@ -0,0 +1,106 @@
+use super::{load_input, parse_seconds, select_days, CmdResult};
+use aoc::bench::{bench_day, compare, BenchOptions, BenchReport};
+use aoc::config::Config;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct BenchOpt {
+    /// The day to benchmark. Benchmarks every day if omitted.
+    day: Option<u32>,
+
+    /// Number of samples to take of each stage
+    #[structopt(short = "n", long, default_value = "100")]
+    samples: usize,
+
+    /// Maximum number of seconds to spend sampling each stage
+    #[structopt(long, default_value = "1", parse(try_from_str = parse_seconds))]
+    max_time: Duration,
+
+    /// Write the results as JSON to this file
+    #[structopt(short, long)]
+    output: Option<PathBuf>,
+
+    /// Benchmark on a generated input of this size instead of the real input
+    #[structopt(short, long)]
+    generated: Option<usize>,
+
+    /// Compare the results against a JSON file written by an earlier run
+    #[structopt(short, long)]
+    baseline: Option<PathBuf>,
+
+    /// Percentage by which a median may get slower before it counts as a regression
+    #[structopt(short, long, default_value = "10")]
+    threshold: f64,
+}
+
+pub fn run(config: &Config, year: u32, opt: BenchOpt) -> CmdResult {
+    let options = BenchOptions {
+        samples: opt.samples,
+        max_time: opt.max_time,
+    };
+
+    // read the baseline first, so that a typo doesn't waste a whole run
+    let baseline: Option<BenchReport> = match &opt.baseline {
+        Some(path) => {
+            let json = fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+            Some(serde_json::from_str(&json)?)
+        }
+        None => None,
+    };
+    if let Some(baseline) = baseline.as_ref().filter(|baseline| baseline.year != year) {
+        return Err(format!("the baseline is of {}, not {}", baseline.year, year).into());
+    }
+
+    let days = select_days(year, opt.day)?;
+    let progress = ProgressBar::new(days.len() as u64 * 3);
+    progress.set_style(ProgressStyle::default_bar().template("{bar:40} {pos}/{len} {msg}"));
+
+    let mut report = BenchReport { year, days: vec![] };
+    for day in days {
+        let input = match opt.generated {
+            Some(size) => day.generate(0, size),
+            None => load_input(config, day, None)?,
+        };
+        let bench = bench_day(day, &input, options, |stage| {
+            progress.set_message(format!("{} {}", day.name, stage));
+            progress.inc(1);
+        })?;
+        report.days.push(bench);
+    }
+    progress.finish_and_clear();
+
+    for day in &report.days {
+        println!("{} parse: {}", day.name, day.parse);
+        println!("{} part1: {}", day.name, day.part1);
+        println!("{} part2: {}", day.name, day.part2);
+    }
+
+    if let Some(path) = &opt.output {
+        fs::write(path, serde_json::to_string_pretty(&report)?)
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    }
+
+    if let Some(baseline) = &baseline {
+        let comparisons = compare(baseline, &report, opt.threshold / 100.0);
+        println!();
+        for comparison in &comparisons {
+            println!("{}", comparison);
+        }
+
+        let regressions = comparisons.iter().filter(|c| c.regression).count();
+        if regressions > 0 {
+            return Err(format!(
+                "{} stages regressed by more than {}%",
+                regressions, opt.threshold
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use super::{read_page, select_days, CmdResult};
+use aoc::config::Config;
+use aoc::fetch::{Client, InputCache};
+use aoc::puzzle::Description;
+use aoc::scaffold::{write_example, Example};
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct ExamplesOpt {
+    /// The day to write the example of
+    day: u32,
+
+    /// Read the puzzle description from this file instead of the input cache
+    #[structopt(short, long)]
+    page: Option<PathBuf>,
+
+    /// Download the puzzle description again, e.g. to get part 2 after solving part 1
+    #[structopt(long)]
+    refresh: bool,
+
+    /// Which of the examples on the page to use, counting from 1
+    #[structopt(short = "n", long, default_value = "1")]
+    example: usize,
+
+    /// What to call the example in the day's `examples` directory
+    #[structopt(long, default_value = "example")]
+    name: String,
+}
+
+pub fn run(config: &Config, year: u32, opt: ExamplesOpt) -> CmdResult {
+    let day = select_days(year, Some(opt.day))?[0];
+    let page = match &opt.page {
+        Some(path) => read_page(path)?,
+        None => InputCache::new(&config.cache).page_or_fetch(
+            &Client::new(config),
+            year,
+            day.number,
+            opt.refresh,
+        )?,
+    };
+
+    let description = Description::parse(&page);
+    let count = description.examples.len();
+    let example = opt
+        .example
+        .checked_sub(1)
+        .and_then(|index| Example::from_description(&description, index))
+        .ok_or_else(|| {
+            format!(
+                "there is no example {}, the page has {}",
+                opt.example, count
+            )
+        })?;
+
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join(day.dir());
+    let path = write_example(&dir, &opt.name, &example)?;
+
+    println!(
+        "wrote example {} of {} to {}",
+        opt.example,
+        count,
+        path.display()
+    );
+    for (part, answer) in [(1, &example.answers.part1), (2, &example.answers.part2)] {
+        match answer {
+            Some(answer) => println!("part{}: {}", part, answer),
+            None => println!("part{}: no answer found", part),
+        }
+    }
+
+    Ok(())
+}
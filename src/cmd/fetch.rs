@@ -0,0 +1,31 @@
+use super::{select_days, CmdResult};
+use aoc::config::Config;
+use aoc::fetch::{Client, InputCache};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct FetchOpt {
+    /// The day to fetch. Fetches every day if omitted.
+    day: Option<u32>,
+
+    /// Download the input even if it is already cached
+    #[structopt(short, long)]
+    force: bool,
+}
+
+pub fn run(config: &Config, year: u32, opt: FetchOpt) -> CmdResult {
+    let client = Client::new(config);
+    let cache = InputCache::new(&config.cache);
+
+    for day in select_days(year, opt.day)? {
+        if opt.force || cache.get(year, day.number)?.is_none() {
+            let input = client.input(year, day.number)?;
+            cache.store(year, day.number, &input)?;
+            println!("fetched {}", cache.path(year, day.number).display());
+        } else {
+            println!("cached {}", cache.path(year, day.number).display());
+        }
+    }
+
+    Ok(())
+}
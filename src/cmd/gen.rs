@@ -0,0 +1,35 @@
+use super::{select_days, CmdResult};
+use std::fs;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct GenOpt {
+    /// The day to generate an input for
+    day: u32,
+
+    /// Size of the input, e.g. the number of lines. What it counts depends on the day.
+    #[structopt(short, long, default_value = "1000")]
+    size: usize,
+
+    /// Seed for the random number generator. The same seed always gives the same input.
+    #[structopt(long, default_value = "0")]
+    seed: u64,
+
+    /// Write the input to this file instead of stdout
+    #[structopt(short, long)]
+    output: Option<PathBuf>,
+}
+
+pub fn run(year: u32, opt: GenOpt) -> CmdResult {
+    let day = select_days(year, Some(opt.day))?[0];
+    let input = day.generate(opt.seed, opt.size);
+
+    match &opt.output {
+        Some(path) => fs::write(path, input)
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))?,
+        None => print!("{}", input),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use super::CmdResult;
+use aoc::config::Config;
+use aoc::days;
+use aoc::fetch::{normalize_session, InputCache};
+use aoc::mock_server::MockServer;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct MockServerOpt {
+    /// Serve the inputs and puzzle descriptions from this input cache directory instead of the
+    /// configured cache
+    #[structopt(long)]
+    inputs: Option<PathBuf>,
+}
+
+pub fn run(config: &Config, opt: MockServerOpt) -> CmdResult {
+    let addr = config
+        .url
+        .strip_prefix("http://")
+        .ok_or("the mock server can only serve plain http:// urls")?
+        .trim_end_matches('/');
+    let session = config
+        .session
+        .as_deref()
+        .map(normalize_session)
+        .ok_or("the mock server needs a session to accept")?;
+
+    let server = MockServer::bind(addr, &session)?;
+    let cache = InputCache::new(opt.inputs.unwrap_or_else(|| config.cache.clone()));
+    for day in days() {
+        if let Some(input) = cache.get(day.year, day.number)? {
+            server.add_input(day.year, day.number, &input);
+        }
+        if let Some(page) = cache.get_page(day.year, day.number)? {
+            server.add_page(day.year, day.number, &page);
+        }
+    }
+
+    println!("serving on {}", server.url());
+    server.wait();
+    Ok(())
+}
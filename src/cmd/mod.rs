@@ -0,0 +1,75 @@
+use aoc::config::Config;
+use aoc::fetch::{Client, InputCache};
+use aoc::DayEntry;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub mod bench;
+pub mod examples;
+pub mod fetch;
+pub mod gen;
+pub mod mock_server;
+pub mod new;
+pub mod run;
+pub mod submit;
+pub mod verify;
+pub mod watch;
+
+pub type CmdResult = Result<(), Box<dyn Error>>;
+
+/// Read the input of a day from `path` if given, otherwise from the cache, fetching it if needed
+pub fn load_input(
+    config: &Config,
+    day: &DayEntry,
+    path: Option<&Path>,
+) -> Result<String, Box<dyn Error>> {
+    match path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e).into()),
+        None => {
+            let cache = InputCache::new(&config.cache);
+            Ok(cache.get_or_fetch(&Client::new(config), day.year, day.number)?)
+        }
+    }
+}
+
+/// Where [load_input] reads the input of a day from
+pub fn input_path(config: &Config, day: &DayEntry, path: Option<&Path>) -> PathBuf {
+    match path {
+        Some(path) => path.to_owned(),
+        None => InputCache::new(&config.cache).path(day.year, day.number),
+    }
+}
+
+/// Read a saved puzzle description page
+pub fn read_page(path: &Path) -> Result<String, Box<dyn Error>> {
+    fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e).into())
+}
+
+/// Parse a command line option given in (fractional) seconds
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = s
+        .parse()
+        .map_err(|_| format!("`{}` is not a number of seconds", s))?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("`{}` is not a valid number of seconds", s))
+}
+
+/// Look up a single day of `year`, or all of them if `number` is `None`
+pub fn select_days(
+    year: u32,
+    number: Option<u32>,
+) -> Result<Vec<&'static DayEntry>, Box<dyn Error>> {
+    match number {
+        Some(number) => match aoc::day(year, number) {
+            Some(day) => Ok(vec![day]),
+            None => Err(format!("day {} of {} is not implemented", number, year).into()),
+        },
+        None => match aoc::registry::days_of(year).collect::<Vec<_>>() {
+            days if days.is_empty() => Err(format!("no days of {} are implemented", year).into()),
+            days => Ok(days),
+        },
+    }
+}
@@ -0,0 +1,55 @@
+use super::{read_page, CmdResult};
+use aoc::answer::Answer;
+use aoc::puzzle::Description;
+use aoc::scaffold::{new_day, Example};
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct NewOpt {
+    /// The day to create
+    day: u32,
+
+    /// A saved puzzle description page. Its first example becomes the test input.
+    #[structopt(short, long)]
+    page: Option<PathBuf>,
+
+    /// The answer to part 1 for the example, instead of the one found on the page
+    #[structopt(long)]
+    part1: Option<String>,
+
+    /// The answer to part 2 for the example, instead of the one found on the page
+    #[structopt(long)]
+    part2: Option<String>,
+}
+
+pub fn run(year: u32, opt: NewOpt) -> CmdResult {
+    if !(1..=25).contains(&opt.day) {
+        return Err(format!("there is no day {}", opt.day).into());
+    }
+
+    let mut example = match &opt.page {
+        Some(path) => {
+            let description = Description::parse(&read_page(path)?);
+            Example::from_description(&description, 0)
+                .ok_or_else(|| format!("found no example in {}", path.display()))?
+        }
+        None => Example::default(),
+    };
+    if let Some(answer) = &opt.part1 {
+        example.answers.part1 = Some(Answer::parse(answer));
+    }
+    if let Some(answer) = &opt.part2 {
+        example.answers.part2 = Some(Answer::parse(answer));
+    }
+
+    let dir = new_day(
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        year,
+        opt.day,
+        &example,
+    )?;
+    println!("created {}", dir.display());
+
+    Ok(())
+}
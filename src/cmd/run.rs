@@ -0,0 +1,296 @@
+use super::{input_path, load_input, parse_seconds, select_days, CmdResult};
+use aoc::alloc_stats;
+use aoc::config::Config;
+use aoc::output::{
+    failed_day, parse_day, run_day, solve_part, stream_day, write_alloc_stats, write_records,
+    write_summary, Format, Record, Status,
+};
+use aoc::DayEntry;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use rayon::ThreadPoolBuilder;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct RunOpt {
+    /// The day to run. Runs every day of the year if omitted.
+    day: Option<u32>,
+
+    /// Run every day of every year
+    #[structopt(long, conflicts_with = "day")]
+    all: bool,
+
+    /// Read the puzzle input from this file instead of the input cache
+    #[structopt(short, long)]
+    input: Option<PathBuf>,
+
+    /// Output format: text, json or csv
+    #[structopt(short, long, default_value = "text")]
+    format: Format,
+
+    /// Give up on parsing or a part after this many seconds, and carry on with the rest
+    #[structopt(short, long, default_value = "60", parse(try_from_str = parse_seconds))]
+    timeout: Duration,
+
+    /// Run the days and their parts at the same time on a pool of threads. The timings are left
+    /// out, as they would be skewed by everything else that is running.
+    #[structopt(short, long)]
+    parallel: bool,
+
+    /// Number of threads to run on with --parallel. Defaults to one per CPU.
+    #[structopt(short, long, requires = "parallel")]
+    jobs: Option<usize>,
+
+    /// After a --parallel run, time the days again one at a time, with nothing else running
+    #[structopt(short, long, requires = "parallel")]
+    measure: bool,
+
+    /// Count the heap allocations of parsing and each part, and show them in a table
+    #[structopt(long)]
+    alloc_stats: bool,
+
+    /// Read the input a line at a time rather than all at once, for inputs too large to fit in
+    /// memory. Only some days can do this, and the rest are skipped. With `-i -` the input is
+    /// read from stdin.
+    #[structopt(short, long, conflicts_with = "parallel")]
+    stream: bool,
+}
+
+/// A day to run, with its input and where that was read from
+type Job = (&'static DayEntry, String, PathBuf);
+
+/// Run every stage of the days on a pool of `jobs` threads, showing what each thread is doing
+///
+/// The parts of a day are scheduled as soon as it is parsed. The records have no timings, but
+/// the allocations are still right, as every stage runs on a thread of its own.
+fn run_parallel(
+    days: &[Job],
+    timeout: Duration,
+    jobs: Option<usize>,
+) -> Result<Vec<Record>, Box<dyn Error>> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()?;
+
+    let progress = MultiProgress::new();
+    let total = progress.add(ProgressBar::new(days.len() as u64 * 3));
+    total.set_style(ProgressStyle::default_bar().template("{bar:40} {pos}/{len}"));
+    let workers: Vec<ProgressBar> = (0..pool.current_num_threads())
+        .map(|_| progress.add(ProgressBar::new_spinner()))
+        .collect();
+
+    let records = Mutex::new(vec![]);
+    let (records_ref, total, workers) = (&records, &total, &workers);
+    let show = |day: &DayEntry, stage: &str| {
+        let worker = rayon::current_thread_index().and_then(|index| workers.get(index));
+        if let Some(worker) = worker {
+            worker.set_message(format!("{}/{:02} {}", day.year, day.number, stage));
+            worker.tick();
+        }
+    };
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            pool.scope(|scope| {
+                for (day, input, path) in days {
+                    scope.spawn(move |scope| {
+                        show(day, "parse");
+                        let parsed = parse_day(day, input, timeout);
+                        total.inc(1);
+
+                        let (parsed, parse_cost) = match parsed {
+                            Ok(parsed) => parsed,
+                            Err(failed) => {
+                                let mut records = records_ref.lock().unwrap();
+                                for part in [1, 2] {
+                                    let path = path.clone();
+                                    let failed = Err(failed.clone());
+                                    records.push(Record::new(day, part, path, None, failed));
+                                }
+                                total.inc(2);
+                                return;
+                            }
+                        };
+
+                        for part in [1, 2] {
+                            let parsed = Arc::clone(&parsed);
+                            scope.spawn(move |_| {
+                                show(day, &format!("part{}", part));
+                                let solved = solve_part(day, part, &parsed, timeout);
+                                let path = path.clone();
+                                let record = Record::new(day, part, path, Some(parse_cost), solved);
+                                records_ref.lock().unwrap().push(Record {
+                                    parse_ns: None,
+                                    solve_ns: None,
+                                    ..record
+                                });
+                                total.inc(1);
+                            });
+                        }
+                    });
+                }
+            });
+
+            for worker in workers {
+                worker.finish_and_clear();
+            }
+            total.finish_and_clear();
+        });
+
+        progress.join_and_clear()
+    })?;
+
+    let mut records = records.into_inner().unwrap();
+    records.sort_by_key(|record| (record.year, record.day, record.part));
+    Ok(records)
+}
+
+/// Time the days again, one at a time, and fill the timings into their records
+///
+/// Days with a part that timed out are skipped, rather than waiting for the timeout again.
+fn measure(days: &[Job], records: &mut [Record], timeout: Duration) {
+    for (day, input, path) in days {
+        let key = |record: &Record| (record.year, record.day);
+        let of_day: Vec<&mut Record> = records
+            .iter_mut()
+            .filter(|record| key(record) == (day.year, day.number))
+            .collect();
+        if of_day
+            .iter()
+            .any(|record| record.status == Status::TimedOut)
+        {
+            continue;
+        }
+
+        let timed = run_day(day, input, path.clone(), timeout);
+        for record in of_day {
+            if let Some(timed) = timed.iter().find(|timed| timed.part == record.part) {
+                record.parse_ns = timed.parse_ns;
+                record.solve_ns = timed.solve_ns;
+            }
+        }
+    }
+}
+
+/// Open the input of a day for [stream_day], fetching it into the cache first if it isn't there
+fn open_stream(
+    config: &Config,
+    day: &DayEntry,
+    path: Option<&Path>,
+) -> Result<impl FnOnce() -> io::Result<Box<dyn BufRead>> + Send + 'static, Box<dyn Error>> {
+    let stdin = path == Some(Path::new("-"));
+    let path = input_path(config, day, path);
+    if !stdin && !path.exists() {
+        load_input(config, day, None)?;
+    }
+
+    Ok(move || -> io::Result<Box<dyn BufRead>> {
+        match stdin {
+            true => Ok(Box::new(io::stdin().lock())),
+            false => Ok(Box::new(BufReader::new(File::open(path)?))),
+        }
+    })
+}
+
+pub fn run(config: &Config, year: u32, opt: RunOpt) -> CmdResult {
+    let stdout = io::stdout();
+    let timeout = opt.timeout;
+    let days = match opt.all {
+        true => aoc::days().iter().collect(),
+        false => select_days(year, opt.day)?,
+    };
+
+    if opt.alloc_stats {
+        alloc_stats::enable();
+    }
+
+    let mut records = vec![];
+    if opt.stream {
+        if opt.input.as_deref() == Some(Path::new("-")) && days.len() != 1 {
+            return Err("only a single day can be read from stdin".into());
+        }
+
+        for day in days {
+            if day.stream.is_none() {
+                let name = format!("day {} of {}", day.number, day.year);
+                if opt.day.is_some() {
+                    return Err(format!("{} can't be streamed", name).into());
+                }
+                eprintln!("skipping {}, which can't be streamed", name);
+                continue;
+            }
+
+            let path = input_path(config, day, opt.input.as_deref());
+            let day_records = match open_stream(config, day, opt.input.as_deref()) {
+                Ok(open) => stream_day(day, open, path, timeout),
+                Err(e) => failed_day(day, path, Status::InputError, e.to_string()),
+            };
+            if opt.format == Format::Text {
+                write_records(opt.format, &mut stdout.lock(), &day_records)?;
+            }
+            records.extend(day_records);
+        }
+    } else if opt.parallel {
+        // the inputs are loaded first, so that any fetching happens one day at a time
+        let (mut jobs, mut unread) = (vec![], vec![]);
+        for day in days {
+            let path = input_path(config, day, opt.input.as_deref());
+            match load_input(config, day, opt.input.as_deref()) {
+                Ok(input) => jobs.push((day, input, path)),
+                Err(e) => unread.extend(failed_day(day, path, Status::InputError, e.to_string())),
+            }
+        }
+
+        records = run_parallel(&jobs, timeout, opt.jobs)?;
+        if opt.measure {
+            measure(&jobs, &mut records, timeout);
+        }
+        records.extend(unread);
+        records.sort_by_key(|record| (record.year, record.day, record.part));
+        if opt.format == Format::Text {
+            write_records(opt.format, &mut stdout.lock(), &records)?;
+        }
+    } else {
+        for day in days {
+            let path = input_path(config, day, opt.input.as_deref());
+            let day_records = match load_input(config, day, opt.input.as_deref()) {
+                Ok(input) => run_day(day, &input, path, timeout),
+                Err(e) => failed_day(day, path, Status::InputError, e.to_string()),
+            };
+
+            // text is written as soon as possible, the other formats need every record first
+            if opt.format == Format::Text {
+                write_records(opt.format, &mut stdout.lock(), &day_records)?;
+            }
+            records.extend(day_records);
+        }
+    }
+
+    // the tables go to stderr for the other formats, to keep stdout machine-readable
+    if opt.format == Format::Text {
+        println!();
+        write_summary(&mut stdout.lock(), &records)?;
+        if opt.alloc_stats {
+            println!();
+            write_alloc_stats(&mut stdout.lock(), &records)?;
+        }
+    } else {
+        write_records(opt.format, &mut stdout.lock(), &records)?;
+        write_summary(&mut io::stderr(), &records)?;
+        if opt.alloc_stats {
+            write_alloc_stats(&mut io::stderr(), &records)?;
+        }
+    }
+
+    let failed = records.iter().filter(|r| r.status != Status::Ok).count();
+    if failed > 0 {
+        return Err(format!("{} parts failed", failed).into());
+    }
+    Ok(())
+}
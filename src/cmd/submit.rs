@@ -0,0 +1,74 @@
+use super::{load_input, select_days, CmdResult};
+use aoc::config::Config;
+use aoc::fetch::Client;
+use aoc::known_answers::{input_id, KnownAnswers};
+use aoc::submit::{submit, Outcome, Response, SubmitError, SubmitLog};
+use std::path::PathBuf;
+use std::thread;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct SubmitOpt {
+    /// The day to submit an answer for
+    day: u32,
+
+    /// The part to submit, 1 or 2
+    part: u32,
+
+    /// Read the puzzle input from this file instead of the input cache
+    #[structopt(short, long)]
+    input: Option<PathBuf>,
+
+    /// Sleep through the cooldown instead of giving up
+    #[structopt(short, long)]
+    wait: bool,
+}
+
+pub fn run(config: &Config, year: u32, opt: SubmitOpt) -> CmdResult {
+    let day = select_days(year, Some(opt.day))?[0];
+    if !(1..=2).contains(&opt.part) {
+        return Err(format!("there is no part {}", opt.part).into());
+    }
+
+    let input = load_input(config, day, opt.input.as_deref())?;
+    let parsed = (day.parse)(&input)?;
+    let answer = match opt.part {
+        1 => (day.part1)(&parsed),
+        _ => (day.part2)(&parsed),
+    }?;
+
+    let client = Client::new(config);
+    let mut log = SubmitLog::load(config.cache.join("submissions.toml"))?;
+
+    println!(
+        "submitting {} for day {} part {} ({})",
+        answer, day.number, opt.part, year
+    );
+    let submitted = answer.to_string();
+    loop {
+        let wait = match submit(&client, &mut log, year, day.number, opt.part, &submitted) {
+            Err(SubmitError::Cooldown(wait)) | Ok(Response::TooSoon(wait)) if opt.wait => wait,
+            // nothing was checked, so scripts must be able to tell from the exit status
+            Ok(Response::TooSoon(wait)) => return Err(SubmitError::Cooldown(wait).into()),
+            Ok(response @ Response::WrongLevel) => return Err(response.to_string().into()),
+            Ok(response) => {
+                println!("{}", response);
+                if let Response::Checked {
+                    outcome: Outcome::Correct,
+                    ..
+                } = response
+                {
+                    let mut known = KnownAnswers::load(&config.answers)?;
+                    known.insert(year, day.number, opt.part, &input_id(&input), answer);
+                    known.save()?;
+                    println!("recorded in {}", known.path().display());
+                }
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        println!("waiting {}s", wait.as_secs());
+        thread::sleep(wait);
+    }
+}
@@ -0,0 +1,68 @@
+use super::{parse_seconds, select_days, CmdResult};
+use aoc::config::Config;
+use aoc::fetch::InputCache;
+use aoc::known_answers::{input_id, KnownAnswers, Verdict};
+use aoc::output::{run_day, Status};
+use std::time::Duration;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct VerifyOpt {
+    /// The day to verify. Verifies every day if omitted.
+    day: Option<u32>,
+
+    /// Give up on parsing or a part after this many seconds, and carry on with the rest
+    #[structopt(short, long, default_value = "60", parse(try_from_str = parse_seconds))]
+    timeout: Duration,
+}
+
+/// Run every day on its cached input and compare against the known answers
+///
+/// A day that fails to parse, panics or times out counts as failed, and the rest still run.
+pub fn run(config: &Config, year: u32, opt: VerifyOpt) -> CmdResult {
+    let known = KnownAnswers::load(&config.answers)?;
+    let cache = InputCache::new(&config.cache);
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for day in select_days(year, opt.day)? {
+        let input = match cache.get(year, day.number)? {
+            Some(input) => input,
+            None => {
+                println!("{}: no cached input", day.name);
+                missing += 2;
+                continue;
+            }
+        };
+
+        let id = input_id(&input);
+        let path = cache.path(year, day.number);
+        for record in run_day(day, &input, path, opt.timeout) {
+            let part = record.part;
+            let answer = match record.answer {
+                Some(answer) if record.status == Status::Ok => answer,
+                _ => {
+                    let error = record.error.unwrap_or_default();
+                    println!("{} part{}: {}: {}", day.name, part, record.status, error);
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            let verdict = known.check(year, day.number, part, &id, &answer);
+            println!("{} part{}: {} {}", day.name, part, answer, verdict);
+            match verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Fail { .. } => failed += 1,
+                Verdict::Missing => missing += 1,
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    if failed > 0 {
+        let path = known.path().display();
+        return Err(format!("{} answers failed or don't match {}", failed, path).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use super::{input_path, select_days, CmdResult};
+use aoc::config::Config;
+use aoc::output::{diff_records, Record};
+use aoc::DayEntry;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use structopt::StructOpt;
+
+/// How often to check the watched files for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(StructOpt)]
+pub struct WatchOpt {
+    /// The day to watch
+    day: u32,
+
+    /// Read the puzzle input from this file instead of the input cache
+    #[structopt(short, long)]
+    input: Option<PathBuf>,
+}
+
+/// The modification times of every file under `paths`, sorted by path
+fn snapshot(paths: &[PathBuf]) -> Vec<(PathBuf, SystemTime)> {
+    fn visit(path: &Path, files: &mut Vec<(PathBuf, SystemTime)>) {
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(_) => return,
+        };
+
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+                visit(&entry.path(), files);
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            files.push((path.to_owned(), modified));
+        }
+    }
+
+    let mut files = vec![];
+    for path in paths {
+        visit(path, &mut files);
+    }
+    files.sort();
+    files
+}
+
+fn cargo(manifest_dir: &Path, command: &str) -> Command {
+    let mut cargo = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    cargo
+        .arg(command)
+        .arg("--release")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"));
+    cargo
+}
+
+/// Rebuild, run the tests of the day and then the day itself in a child process
+///
+/// Returns `None` if the day couldn't be run, e.g. because it doesn't compile.
+fn rerun(
+    manifest_dir: &Path,
+    config_path: &Path,
+    opt: &WatchOpt,
+    day: &DayEntry,
+) -> Option<Vec<Record>> {
+    let tests = cargo(manifest_dir, "test")
+        .args(["--lib", "--", &format!("{}::", day.module())])
+        .stdout(Stdio::null())
+        .status();
+    match tests {
+        Ok(status) if status.success() => println!("tests passed"),
+        Ok(_) => println!("tests FAILED"),
+        Err(e) => {
+            println!("failed to run cargo: {}", e);
+            return None;
+        }
+    }
+
+    let mut run = cargo(manifest_dir, "run");
+    run.arg("--").arg("--config").arg(config_path).args([
+        "--year",
+        &day.year.to_string(),
+        "run",
+        &opt.day.to_string(),
+        "--format",
+        "json",
+    ]);
+    if let Some(input) = &opt.input {
+        run.arg("--input").arg(input);
+    }
+
+    // a failed part still produces records, so only the output matters
+    let output = run.stderr(Stdio::inherit()).output().ok()?;
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// Re-run a day whenever its source, its example input or its real input changes
+pub fn run(config: &Config, config_path: &Path, year: u32, opt: WatchOpt) -> CmdResult {
+    let day = select_days(year, Some(opt.day))?[0];
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let watched = [
+        manifest_dir.join("src").join(day.dir()),
+        input_path(config, day, opt.input.as_deref()),
+    ];
+    for path in &watched {
+        println!("watching {}", path.display());
+    }
+
+    let mut previous: Vec<Record> = vec![];
+    let mut last_snapshot = None;
+    loop {
+        let current = snapshot(&watched);
+        if last_snapshot.as_ref() != Some(&current) {
+            last_snapshot = Some(current);
+
+            println!();
+            if let Some(records) = rerun(manifest_dir, config_path, &opt, day) {
+                for line in diff_records(&previous, &records) {
+                    println!("{}", line);
+                }
+                previous = records;
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,101 @@
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Settings read from `config.toml`, see `config.toml.example`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    /// URL to the Advent of Code website
+    #[serde(default = "default_url")]
+    pub url: String,
+
+    /// Session cookie for the Advent of Code website
+    #[serde(default)]
+    pub session: Option<String>,
+
+    /// Directory where fetched inputs are cached
+    #[serde(default = "default_cache")]
+    pub cache: PathBuf,
+
+    /// File with answers confirmed for the real inputs
+    #[serde(default = "default_answers")]
+    pub answers: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, toml::de::Error),
+}
+
+fn default_url() -> String {
+    "https://adventofcode.com".to_string()
+}
+
+fn default_cache() -> PathBuf {
+    PathBuf::from("inputs")
+}
+
+fn default_answers() -> PathBuf {
+    PathBuf::from("answers.toml")
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            url: default_url(),
+            session: None,
+            cache: default_cache(),
+            answers: default_answers(),
+        }
+    }
+}
+
+impl Config {
+    /// Read the config file at `path`, or use the defaults if it doesn't exist
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(ConfigError::Io(path.to_owned(), e)),
+        };
+
+        toml::from_str(&text).map_err(|e| ConfigError::Toml(path.to_owned(), e))
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "failed to read {}: {}", path.display(), e),
+            ConfigError::Toml(path, e) => write!(f, "invalid config {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use std::path::Path;
+
+    #[test]
+    pub fn test_defaults() {
+        let config: Config = toml::from_str(r#"session = "abc123""#).unwrap();
+        assert_eq!(config.url, "https://adventofcode.com");
+        assert_eq!(config.session.as_deref(), Some("abc123"));
+        assert_eq!(config.cache, Path::new("inputs"));
+        assert_eq!(config.answers, Path::new("answers.toml"));
+    }
+
+    #[test]
+    pub fn test_example() {
+        let example = include_str!("../config.toml.example");
+        let config: Config = toml::from_str(example).unwrap();
+        assert_eq!(config.session, None);
+    }
+}
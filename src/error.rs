@@ -0,0 +1,209 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+/// An error encountered while parsing the puzzle input of a day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+
+    /// 1-indexed line of the offending token
+    pub line: usize,
+
+    /// 1-indexed column of the offending token, counted in chars
+    pub column: usize,
+
+    pub token: String,
+    pub kind: ParseErrorKind,
+}
+
+/// An error encountered while solving a part of a day, for input that parsed but that the puzzle
+/// has no answer for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    pub day: u32,
+    pub part: u32,
+
+    /// Why there is no answer, e.g. "no board ever wins"
+    pub reason: &'static str,
+}
+
+/// An error encountered while reading the puzzle input of a day from a stream
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended before all expected data was read
+    UnexpectedEnd,
+
+    /// The token could not be parsed as a number
+    InvalidNumber,
+
+    /// The token was not one of the expected values
+    UnexpectedToken,
+
+    /// The token did not contain the expected delimiter
+    MissingDelimiter(&'static str),
+
+    /// A group of tokens had the wrong number of elements
+    WrongLength { expected: usize, found: usize },
+
+    /// The number is outside the range the puzzle allows
+    OutOfRange,
+}
+
+impl ParseError {
+    /// Create an error pointing at `token`, which should be a slice of `input`.
+    ///
+    /// The line and column are derived from where `token` lives in `input`. A token that is not
+    /// part of `input` is reported at the very end of it.
+    pub fn new(day: u32, input: &str, token: &str, kind: ParseErrorKind) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(input.len());
+
+        let before = &input[..floor_char_boundary(input, offset)];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+
+        ParseError {
+            day,
+            line,
+            column,
+            token: token.to_string(),
+            kind,
+        }
+    }
+
+    /// Move an error found in a piece of the input to where that piece starts in the whole input
+    ///
+    /// For parsers that only see a piece of the input at a time. `line` and `column` are where
+    /// the piece starts, 1-indexed.
+    pub fn shifted(mut self, line: usize, column: usize) -> Self {
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+}
+
+fn floor_char_boundary(s: &str, mut i: usize) -> usize {
+    while !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ParseErrorKind::UnexpectedToken => write!(f, "unexpected token"),
+            ParseErrorKind::MissingDelimiter(delim) => write!(f, "missing delimiter {:?}", delim),
+            ParseErrorKind::WrongLength { expected, found } => {
+                write!(f, "expected {} elements, found {}", expected, found)
+            }
+            ParseErrorKind::OutOfRange => write!(f, "number out of range"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.kind
+        )?;
+
+        if !self.token.is_empty() {
+            write!(f, ": {:?}", self.token)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+impl SolveError {
+    pub fn new(day: u32, part: u32, reason: &'static str) -> Self {
+        SolveError { day, part, reason }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, part {}: no solution: {}",
+            self.day, self.part, self.reason
+        )
+    }
+}
+
+impl Error for SolveError {}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "failed to read the input: {}", e),
+            StreamError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> Self {
+        StreamError::Parse(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, ParseErrorKind};
+
+    #[test]
+    pub fn test_position() {
+        let input = "123\n45x6\n789";
+        let token = &input[6..7];
+        let error = ParseError::new(1, input, token, ParseErrorKind::InvalidNumber);
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.token, "x");
+    }
+
+    #[test]
+    pub fn test_shifted() {
+        let line = "45x6";
+        let error = ParseError::new(1, line, &line[2..3], ParseErrorKind::InvalidNumber);
+        let error = error.shifted(2, 1);
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = ParseError::new(1, "1\n2", "", ParseErrorKind::UnexpectedEnd).shifted(3, 5);
+        assert_eq!((error.line, error.column), (4, 2));
+    }
+
+    #[test]
+    pub fn test_foreign_token() {
+        let input = "1\n2\n";
+        let error = ParseError::new(1, input, "", ParseErrorKind::UnexpectedEnd);
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+}
@@ -0,0 +1,32 @@
+//! A test for each part of every example in `src/yearYYYY/dayNN/examples`, made by `build.rs`
+//!
+//! The tests are named like `examples::year2021::day08::small_part2`, so dropping `small.txt` and
+//! `small.toml` into `src/year2021/day08/examples` is all it takes to test another example.
+
+use crate::testing::check_example;
+
+macro_rules! example_test {
+    (
+        $(#[ignore = $reason:literal])?
+        $name:ident: $year:literal, $day:literal, $part:literal, $input:literal, $answers:literal
+    ) => {
+        #[test]
+        $(#[ignore = $reason])?
+        pub fn $name() {
+            let answers = Some(include_str!($answers));
+            check_example($year, $day, $part, include_str!($input), answers);
+        }
+    };
+    (
+        #[ignore = $reason:literal]
+        $name:ident: $year:literal, $day:literal, $part:literal, $input:literal
+    ) => {
+        #[test]
+        #[ignore = $reason]
+        pub fn $name() {
+            check_example($year, $day, $part, include_str!($input), None);
+        }
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
@@ -0,0 +1,246 @@
+use crate::config::Config;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const USER_AGENT: &str = concat!(
+    "github.com/hulthe/aoc2021 ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// No session cookie was configured
+    NoSession,
+
+    /// The server responded with an error status
+    Status {
+        status: u16,
+        body: String,
+    },
+
+    /// The request could not be made at all
+    Transport(String),
+
+    Io(PathBuf, io::Error),
+}
+
+/// A client for the Advent of Code website, or anything pretending to be it
+pub struct Client {
+    url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Self {
+        Client {
+            url: config.url.trim_end_matches('/').to_string(),
+            session: config.session.as_deref().map(normalize_session),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// The value of the `Cookie` header to send with every request
+    fn cookie(&self) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::NoSession)?;
+        Ok(format!("session={}", session))
+    }
+
+    /// GET a path relative to the configured url
+    fn get(&self, path: &str) -> Result<String, FetchError> {
+        let request = self
+            .agent
+            .get(&format!("{}{}", self.url, path))
+            .set("Cookie", &self.cookie()?);
+
+        read_response(request.call())
+    }
+
+    /// POST a form to a path relative to the configured url
+    fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, FetchError> {
+        let request = self
+            .agent
+            .post(&format!("{}{}", self.url, path))
+            .set("Cookie", &self.cookie()?);
+
+        read_response(request.send_form(form))
+    }
+
+    /// Download the puzzle input of a day
+    pub fn input(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// Download the puzzle description of a day, which includes part 2 once part 1 is solved
+    pub fn puzzle(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        self.get(&format!("/{}/day/{}", year, day))
+    }
+
+    /// Submit the answer to one part of a day, returning the page the server responds with
+    pub fn answer(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<String, FetchError> {
+        let level = part.to_string();
+        self.post_form(
+            &format!("/{}/day/{}/answer", year, day),
+            &[("level", &level), ("answer", answer)],
+        )
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, FetchError> {
+    let into_string = |response: ureq::Response| {
+        response
+            .into_string()
+            .map_err(|e| FetchError::Transport(e.to_string()))
+    };
+
+    match response {
+        Ok(response) => into_string(response),
+        Err(ureq::Error::Status(status, response)) => Err(FetchError::Status {
+            status,
+            body: into_string(response)?,
+        }),
+        Err(ureq::Error::Transport(e)) => Err(FetchError::Transport(e.to_string())),
+    }
+}
+
+/// Accept the session both as the bare token and as copied from a `session=...` cookie
+pub fn normalize_session(session: &str) -> String {
+    let session = session.trim();
+    session
+        .strip_prefix("session=")
+        .unwrap_or(session)
+        .trim_end_matches(';')
+        .to_string()
+}
+
+/// Fetched puzzle inputs, stored on disk as `<root>/<year>/dayNN.txt`
+///
+/// Puzzle descriptions are stored next to them, as `dayNN.html`.
+pub struct InputCache {
+    root: PathBuf,
+}
+
+fn read_cached(path: PathBuf) -> Result<Option<String>, FetchError> {
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(FetchError::Io(path, e)),
+    }
+}
+
+fn store_cached(path: PathBuf, text: &str) -> Result<(), FetchError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| FetchError::Io(dir.to_owned(), e))?;
+    }
+    fs::write(&path, text).map_err(|e| FetchError::Io(path, e))
+}
+
+impl InputCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        InputCache { root: root.into() }
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    pub fn page_path(&self, year: u32, day: u32) -> PathBuf {
+        self.path(year, day).with_extension("html")
+    }
+
+    /// Get a cached input, if there is one
+    pub fn get(&self, year: u32, day: u32) -> Result<Option<String>, FetchError> {
+        read_cached(self.path(year, day))
+    }
+
+    /// Get a cached puzzle description, if there is one
+    pub fn get_page(&self, year: u32, day: u32) -> Result<Option<String>, FetchError> {
+        read_cached(self.page_path(year, day))
+    }
+
+    pub fn store(&self, year: u32, day: u32, input: &str) -> Result<(), FetchError> {
+        store_cached(self.path(year, day), input)
+    }
+
+    /// Get a cached input, or download and cache it if it's missing
+    pub fn get_or_fetch(&self, client: &Client, year: u32, day: u32) -> Result<String, FetchError> {
+        if let Some(input) = self.get(year, day)? {
+            return Ok(input);
+        }
+
+        let input = client.input(year, day)?;
+        self.store(year, day, &input)?;
+        Ok(input)
+    }
+
+    /// Get a cached puzzle description, or download and cache it if it's missing
+    ///
+    /// Pass `refresh` to download it again anyway, e.g. to see part 2 after solving part 1.
+    pub fn page_or_fetch(
+        &self,
+        client: &Client,
+        year: u32,
+        day: u32,
+        refresh: bool,
+    ) -> Result<String, FetchError> {
+        if !refresh {
+            if let Some(page) = self.get_page(year, day)? {
+                return Ok(page);
+            }
+        }
+
+        let page = client.puzzle(year, day)?;
+        store_cached(self.page_path(year, day), &page)?;
+        Ok(page)
+    }
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession => write!(f, "no session cookie configured in config.toml"),
+            FetchError::Status { status, body } => {
+                write!(f, "server responded with {}: {}", status, body.trim())
+            }
+            FetchError::Transport(e) => write!(f, "request failed: {}", e),
+            FetchError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_session, InputCache};
+    use std::path::Path;
+
+    #[test]
+    pub fn test_normalize_session() {
+        assert_eq!(normalize_session("abc123"), "abc123");
+        assert_eq!(normalize_session(" session=abc123;\n"), "abc123");
+    }
+
+    #[test]
+    pub fn test_cache_path() {
+        let cache = InputCache::new("inputs");
+        assert_eq!(cache.path(2021, 3), Path::new("inputs/2021/day03.txt"));
+        assert_eq!(
+            cache.page_path(2021, 3),
+            Path::new("inputs/2021/day03.html")
+        );
+    }
+}
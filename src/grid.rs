@@ -0,0 +1,280 @@
+//! A rectangle of cells, for the puzzles that take place on a map
+//!
+//! Cells are stored row by row in a single `Vec`, and addressed by `(x, y)` with `(0, 0)` in the
+//! top left corner, like the rasters in the puzzle inputs.
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::parsing::Source;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+/// A position on a [Grid], as `(x, y)`
+pub type Coord = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+
+    /// Row-major, so the cell at `(x, y)` is at `y * width + x`
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A grid with each cell set to what `f` returns for its coordinate
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coord) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parse a raster of chars, one row per line, turning each char into a cell with `cell`
+    ///
+    /// Every row must be as wide as the first, not counting trailing whitespace. Chars that
+    /// `cell` returns `None` for are reported as unexpected tokens of `day`.
+    pub fn parse_chars(
+        day: u32,
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let source = Source::new(day, input);
+        let error = |token: &str, kind| source.error(token, kind);
+        let width = source.lines().next().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(error("", ParseErrorKind::UnexpectedEnd));
+        }
+
+        let mut cells = vec![];
+        let mut height = 0;
+        for line in source.lines() {
+            let found = line.chars().count();
+            if found != width {
+                let kind = ParseErrorKind::WrongLength {
+                    expected: width,
+                    found,
+                };
+                return Err(error(line, kind));
+            }
+
+            for (i, c) in line.char_indices() {
+                let token = &line[i..i + c.len_utf8()];
+                cells.push(cell(c).ok_or_else(|| error(token, ParseErrorKind::UnexpectedToken))?);
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `coord` is on the grid
+    pub fn contains(&self, (x, y): Coord) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.contains(coord)
+            .then(|| &self.cells[coord.1 * self.width + coord.0])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        match self.contains(coord) {
+            true => Some(&mut self.cells[coord.1 * self.width + coord.0]),
+            false => None,
+        }
+    }
+
+    /// Every coordinate on the grid, row by row
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell, row by row
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// The rows, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, and a grid without columns has no cells to chunk anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The coordinates left, right, above and below `coord`, in that order, that are on the grid
+    pub fn neighbors4(&self, (x, y): Coord) -> impl Iterator<Item = Coord> {
+        let (width, height) = (self.width, self.height);
+        [
+            (x.wrapping_sub(1), y),
+            (x.wrapping_add(1), y),
+            (x, y.wrapping_sub(1)),
+            (x, y.wrapping_add(1)),
+        ]
+        .into_iter()
+        .filter(move |&(x, y)| x < width && y < height)
+    }
+
+    /// The coordinates around `coord`, diagonals included, row by row, that are on the grid
+    pub fn neighbors8(&self, (x, y): Coord) -> impl Iterator<Item = Coord> {
+        let (width, height) = (self.width, self.height);
+        [-1isize, 0, 1]
+            .into_iter()
+            .flat_map(|dy| [-1isize, 0, 1].into_iter().map(move |dx| (dx, dy)))
+            .filter(|&offset| offset != (0, 0))
+            .map(move |(dx, dy)| (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)))
+            .filter(move |&(x, y)| x < width && y < height)
+    }
+
+    /// A grid of the same size, with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parse a raster of decimal digits, like a heightmap
+    pub fn parse_digits(day: u32, input: &str) -> Result<Self, ParseError> {
+        Grid::parse_chars(day, input, |c| c.to_digit(10).map(|d| d as u8)).map_err(|e| {
+            match e.kind {
+                ParseErrorKind::UnexpectedToken => ParseError {
+                    kind: ParseErrorKind::InvalidNumber,
+                    ..e
+                },
+                _ => e,
+            }
+        })
+    }
+}
+
+/// Panics if `coord` is not on the grid
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", coord, width, height))
+    }
+}
+
+/// Panics if `coord` is not on the grid
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", coord, width, height))
+    }
+}
+
+/// One line per row, with the cells written next to each other
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::error::ParseErrorKind;
+
+    #[test]
+    pub fn test_parse_and_display() {
+        let input = "2199\n3987\n9856\n";
+        let grid = Grid::parse_digits(9, input).unwrap();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid[(3, 1)], 7);
+        assert_eq!(grid.get((4, 1)), None);
+        assert_eq!(grid.to_string(), input);
+
+        let grid = Grid::parse_chars(1, "#.\n.#", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        });
+        assert_eq!(
+            grid.unwrap().map(|&wall| wall as u8).to_string(),
+            "10\n01\n"
+        );
+    }
+
+    #[test]
+    pub fn test_parse_errors() {
+        let error = Grid::parse_digits(9, "219\n39x\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.kind),
+            (2, 3, ParseErrorKind::InvalidNumber)
+        );
+
+        let error = Grid::parse_digits(9, "219\n39\n").unwrap_err();
+        let wrong_length = ParseErrorKind::WrongLength {
+            expected: 3,
+            found: 2,
+        };
+        assert_eq!((error.line, error.kind), (2, wrong_length));
+
+        let error = Grid::parse_chars(9, "", |_| Some(())).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedEnd);
+    }
+
+    #[test]
+    pub fn test_neighbors() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(grid[(2, 1)], 12);
+
+        let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, [(1, 0), (0, 1)]);
+        let middle: Vec<_> = grid.neighbors4((1, 1)).collect();
+        assert_eq!(middle, [(0, 1), (2, 1), (1, 0)]);
+
+        let around: Vec<_> = grid.neighbors8((1, 0)).collect();
+        assert_eq!(around, [(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside the 3x2 grid")]
+    pub fn test_out_of_bounds() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid[(3, 0)] += 1;
+    }
+}
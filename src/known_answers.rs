@@ -0,0 +1,211 @@
+//! Answers confirmed by the server for real puzzle inputs
+//!
+//! Stored as TOML, one table per input:
+//!
+//! ```toml
+//! [2021.day01.0a1b2c3d4e5f6789]
+//! part1 = 1292
+//! part2 = 1262
+//! ```
+//!
+//! The last key is the [input_id] of the input the answers belong to, since everyone gets a
+//! different input.
+
+use crate::answer::Answer;
+use hashers::fnv::fnv1a64;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The known answers to both parts of one input
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Parts {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Answer>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Answer>,
+}
+
+/// Year, then day, then input id
+type Table = BTreeMap<String, BTreeMap<String, BTreeMap<String, Parts>>>;
+
+pub struct KnownAnswers {
+    path: PathBuf,
+    table: Table,
+}
+
+/// How a computed answer compares to the known answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: Answer,
+    },
+
+    /// There is no known answer to compare with
+    Missing,
+}
+
+#[derive(Debug)]
+pub enum KnownAnswersError {
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, String),
+}
+
+/// A short, stable id of a puzzle input. Trailing whitespace is ignored.
+pub fn input_id(input: &str) -> String {
+    format!("{:016x}", fnv1a64(input.trim_end().as_bytes()))
+}
+
+impl Parts {
+    pub fn get(&self, part: u32) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn get_mut(&mut self, part: u32) -> &mut Option<Answer> {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            _ => panic!("there is no part {}", part),
+        }
+    }
+}
+
+impl KnownAnswers {
+    /// Read the answers at `path`, or start with none if it doesn't exist
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, KnownAnswersError> {
+        let path = path.into();
+        let table = match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| KnownAnswersError::Toml(path.clone(), e.to_string()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Table::new(),
+            Err(e) => return Err(KnownAnswersError::Io(path, e)),
+        };
+
+        Ok(KnownAnswers { path, table })
+    }
+
+    pub fn save(&self) -> Result<(), KnownAnswersError> {
+        let text = toml::to_string(&self.table)
+            .map_err(|e| KnownAnswersError::Toml(self.path.clone(), e.to_string()))?;
+        fs::write(&self.path, text).map_err(|e| KnownAnswersError::Io(self.path.clone(), e))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The known answers for an input, if any
+    pub fn get(&self, year: u32, day: u32, input_id: &str) -> Option<&Parts> {
+        self.table
+            .get(&year.to_string())?
+            .get(&format!("day{:02}", day))?
+            .get(input_id)
+    }
+
+    /// Remember the answer to one part, replacing any previous answer
+    pub fn insert(&mut self, year: u32, day: u32, part: u32, input_id: &str, answer: Answer) {
+        let parts = self
+            .table
+            .entry(year.to_string())
+            .or_default()
+            .entry(format!("day{:02}", day))
+            .or_default()
+            .entry(input_id.to_string())
+            .or_default();
+        *parts.get_mut(part) = Some(answer);
+    }
+
+    /// Compare a computed answer against the known one
+    pub fn check(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        input_id: &str,
+        answer: &Answer,
+    ) -> Verdict {
+        match self
+            .get(year, day, input_id)
+            .and_then(|parts| parts.get(part))
+        {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+impl Display for KnownAnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            KnownAnswersError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            KnownAnswersError::Toml(path, e) => {
+                write!(f, "invalid answers {}: {}", path.display(), e)
+            }
+        }
+    }
+}
+
+impl Error for KnownAnswersError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_id, KnownAnswers, Verdict};
+    use crate::answer::Answer;
+
+    #[test]
+    pub fn test_input_id() {
+        assert_eq!(input_id("199\n200\n"), input_id("199\n200"));
+        assert_ne!(input_id("199\n200\n"), input_id("199\n201\n"));
+        assert_eq!(input_id("").len(), 16);
+    }
+
+    #[test]
+    pub fn test_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.toml");
+        let id = input_id("199\n200\n");
+
+        let mut known = KnownAnswers::load(&path).unwrap();
+        known.insert(2021, 1, 1, &id, 7.into());
+        known.insert(2021, 8, 2, &id, "abc".into());
+        known.save().unwrap();
+
+        let known = KnownAnswers::load(&path).unwrap();
+        assert_eq!(known.check(2021, 1, 1, &id, &7u64.into()), Verdict::Pass);
+        assert_eq!(
+            known.check(2021, 1, 1, &id, &8.into()),
+            Verdict::Fail {
+                expected: Answer::Signed(7)
+            }
+        );
+        assert_eq!(known.check(2021, 1, 2, &id, &5.into()), Verdict::Missing);
+        assert_eq!(known.check(2021, 8, 2, &id, &"abc".into()), Verdict::Pass);
+        assert_eq!(
+            known.check(2021, 1, 1, "other", &7.into()),
+            Verdict::Missing
+        );
+    }
+}
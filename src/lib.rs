@@ -0,0 +1,25 @@
+pub mod alloc_stats;
+pub mod answer;
+pub mod bench;
+pub mod config;
+pub mod error;
+#[cfg(test)]
+mod examples;
+pub mod fetch;
+pub mod grid;
+pub mod known_answers;
+pub mod mock_server;
+pub mod output;
+pub mod parsing;
+pub mod puzzle;
+pub mod registry;
+pub mod scaffold;
+pub mod submit;
+#[cfg(test)]
+mod testing;
+#[macro_use]
+pub mod solution;
+
+pub mod year2021;
+
+pub use registry::{day, days, latest_year, DayEntry};
@@ -1,6 +1,102 @@
-#![feature(test)]
-#![feature(array_zip, derive_default_enum)]
-extern crate test;
+mod cmd;
 
-const YEAR: u32 = 2021;
-aoc_macro::generate_days! {}
+use aoc::alloc_stats::Counting;
+use aoc::config::Config;
+use aoc::latest_year;
+use cmd::bench::BenchOpt;
+use cmd::examples::ExamplesOpt;
+use cmd::fetch::FetchOpt;
+use cmd::gen::GenOpt;
+use cmd::mock_server::MockServerOpt;
+use cmd::new::NewOpt;
+use cmd::run::RunOpt;
+use cmd::submit::SubmitOpt;
+use cmd::verify::VerifyOpt;
+use cmd::watch::WatchOpt;
+use std::path::PathBuf;
+use std::process::exit;
+use structopt::StructOpt;
+
+/// Counts nothing unless asked to, see `run --alloc-stats`
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[derive(StructOpt)]
+#[structopt(about = "Solver for Advent of Code")]
+struct Opt {
+    /// Path to the config file
+    #[structopt(short, long, default_value = "config.toml")]
+    config: PathBuf,
+
+    /// The year to work on. Defaults to the latest year with any days implemented.
+    #[structopt(long, global = true)]
+    year: Option<u32>,
+
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Solve a day, or every day
+    Run(RunOpt),
+
+    /// Time every stage of a day, or every day, repeatedly
+    Bench(BenchOpt),
+
+    /// Download puzzle inputs into the input cache
+    Fetch(FetchOpt),
+
+    /// Write the example and its answers from the puzzle description as a day's test fixtures
+    Examples(ExamplesOpt),
+
+    /// Generate a random puzzle input for a day
+    Gen(GenOpt),
+
+    /// Create a new day from the template, with its example as the test input
+    New(NewOpt),
+
+    /// Submit the answer to one part of a day
+    Submit(SubmitOpt),
+
+    /// Check every day against the answers confirmed for the real inputs
+    Verify(VerifyOpt),
+
+    /// Rebuild and re-run a day whenever its source or input changes
+    Watch(WatchOpt),
+
+    /// Pretend to be the Advent of Code website on the configured url, serving cached inputs
+    MockServer(MockServerOpt),
+}
+
+fn main() {
+    let opt = Opt::from_args();
+
+    let year = match opt.year.or_else(latest_year) {
+        Some(year) => year,
+        None => {
+            eprintln!("error: no days are implemented yet, pick a year with --year");
+            exit(1);
+        }
+    };
+
+    let result = Config::load(&opt.config)
+        .map_err(Into::into)
+        .and_then(|config| match opt.command {
+            Command::Run(run) => cmd::run::run(&config, year, run),
+            Command::Bench(bench) => cmd::bench::run(&config, year, bench),
+            Command::Fetch(fetch) => cmd::fetch::run(&config, year, fetch),
+            Command::Examples(examples) => cmd::examples::run(&config, year, examples),
+            Command::Gen(gen) => cmd::gen::run(year, gen),
+            Command::New(new) => cmd::new::run(year, new),
+            Command::Submit(submit) => cmd::submit::run(&config, year, submit),
+            Command::Verify(verify) => cmd::verify::run(&config, year, verify),
+            Command::Watch(watch) => cmd::watch::run(&config, &opt.config, year, watch),
+            Command::MockServer(mock) => cmd::mock_server::run(&config, mock),
+        });
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        exit(1);
+    }
+}
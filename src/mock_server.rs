@@ -0,0 +1,395 @@
+//! A local stand-in for the Advent of Code website
+//!
+//! Speaks just enough HTTP/1.1 to serve the endpoints that the [fetch](crate::fetch) and
+//! [submit](crate::submit) modules use, so that they can be tested without network access.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How long the real server makes you wait after a wrong answer
+const WRONG_ANSWER_WAIT: Duration = Duration::from_secs(60);
+
+/// A request received by the [MockServer]
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+#[derive(Default)]
+struct State {
+    session: String,
+    inputs: HashMap<(u32, u32), String>,
+    pages: HashMap<(u32, u32), String>,
+
+    /// The correct answers, keyed by year, day and part
+    answers: HashMap<(u32, u32, u32), String>,
+    solved: HashSet<(u32, u32, u32)>,
+    wait_until: Option<Instant>,
+
+    requests: Vec<Request>,
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Start a server on a free local port, accepting only the given session
+    pub fn start(session: &str) -> io::Result<Self> {
+        MockServer::bind("127.0.0.1:0", session)
+    }
+
+    pub fn bind(addr: impl ToSocketAddrs, session: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+
+        let state = Arc::new(Mutex::new(State {
+            session: session.to_string(),
+            ..State::default()
+        }));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let state = Arc::clone(&state);
+            let running = Arc::clone(&running);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if !running.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    // a broken connection only affects that one request
+                    if let Ok(stream) = stream {
+                        let _ = handle(stream, &state);
+                    }
+                }
+            })
+        };
+
+        Ok(MockServer {
+            addr,
+            state,
+            running,
+            thread: Some(thread),
+        })
+    }
+
+    /// The url to configure a [Client](crate::fetch::Client) with
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn add_input(&self, year: u32, day: u32, input: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.inputs.insert((year, day), input.to_string());
+    }
+
+    /// Set the puzzle description page of a day
+    pub fn add_page(&self, year: u32, day: u32, html: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.pages.insert((year, day), html.to_string());
+    }
+
+    /// Set the correct answer to a part, which is needed before it can be submitted
+    pub fn add_answer(&self, year: u32, day: u32, part: u32, answer: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.answers.insert((year, day, part), answer.to_string());
+    }
+
+    /// All requests received so far
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Block until the server stops, which it never does on its own
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            self.running.store(false, Ordering::SeqCst);
+
+            // wake up the listener so that it notices
+            let _ = TcpStream::connect(self.addr);
+            let _ = thread.join();
+        }
+    }
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let request = read_request(&mut reader)?;
+
+    let response = {
+        let mut state = state.lock().unwrap();
+        let response = route(&request, &mut state);
+        state.requests.push(request);
+        response
+    };
+
+    write_response(stream, response)
+}
+
+fn read_request(reader: &mut impl BufRead) -> io::Result<Request> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid request");
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut request_line = line.split_whitespace();
+    let method = request_line.next().ok_or_else(invalid)?.to_string();
+    let path = request_line.next().ok_or_else(invalid)?.to_string();
+
+    let mut cookie = None;
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        let (name, value) = header.split_once(':').ok_or_else(invalid)?;
+        let value = value.trim();
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = Some(value.to_string()),
+            "content-length" => content_length = value.parse().map_err(|_| invalid())?,
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8(body).map_err(|_| invalid())?;
+
+    Ok(Request {
+        method,
+        path,
+        cookie,
+        body,
+    })
+}
+
+fn write_response(mut stream: TcpStream, response: Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: text/plain\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\
+         \r\n\
+         {}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Get the value of the session cookie, if any
+fn session(cookie: &str) -> Option<&str> {
+    cookie
+        .split(';')
+        .filter_map(|c| c.trim().split_once('='))
+        .find(|&(name, _)| name == "session")
+        .map(|(_, value)| value)
+}
+
+/// Decode an `application/x-www-form-urlencoded` body
+fn form(body: &str) -> HashMap<String, String> {
+    let decode = |s: &str| {
+        let s = s.replace('+', " ");
+        let mut bytes = Vec::with_capacity(s.len());
+        let mut rest = s.as_bytes();
+        while let Some((&b, tail)) = rest.split_first() {
+            let hex = tail.get(..2).and_then(|hex| std::str::from_utf8(hex).ok());
+            match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                Some(decoded) if b == b'%' => {
+                    bytes.push(decoded);
+                    rest = &tail[2..];
+                }
+                _ => {
+                    bytes.push(b);
+                    rest = tail;
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(name, value)| (decode(name), decode(value)))
+        .collect()
+}
+
+/// A page with a message in an `<article>`, like the real server responds with
+fn page(message: &str) -> Response {
+    Response {
+        status: 200,
+        body: format!(
+            "<!DOCTYPE html>\n<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>\n",
+            message
+        ),
+    }
+}
+
+fn answer(state: &mut State, (year, day, part): (u32, u32, u32), answer: &str) -> Response {
+    let now = Instant::now();
+    if let Some(left) = state
+        .wait_until
+        .and_then(|until| until.checked_duration_since(now))
+    {
+        let (minutes, seconds) = (left.as_secs() / 60, left.as_secs() % 60);
+        let left = if minutes > 0 {
+            format!("{}m {}s", minutes, seconds)
+        } else {
+            format!("{}s", seconds)
+        };
+        return page(&format!(
+            "You gave an answer too recently; you have to wait after submitting an answer before \
+             trying again.  You have {} left to wait.",
+            left
+        ));
+    }
+
+    let solved_before = (1..part).all(|part| state.solved.contains(&(year, day, part)));
+    if !solved_before || state.solved.contains(&(year, day, part)) {
+        return page("You don't seem to be solving the right level.  Did you already complete it?");
+    }
+
+    let correct = match state.answers.get(&(year, day, part)) {
+        Some(correct) => correct,
+        None => {
+            return Response {
+                status: 404,
+                body: "404 Not Found".to_string(),
+            }
+        }
+    };
+
+    if answer == correct {
+        state.solved.insert((year, day, part));
+        return page("That's the right answer!  You are <em>one gold star</em> closer.");
+    }
+
+    let hint = match (answer.parse::<i128>(), correct.parse::<i128>()) {
+        (Ok(answer), Ok(correct)) if answer > correct => "; your answer is too high",
+        (Ok(answer), Ok(correct)) if answer < correct => "; your answer is too low",
+        _ => "",
+    };
+    state.wait_until = Some(now + WRONG_ANSWER_WAIT);
+    page(&format!(
+        "That's not the right answer{}.  If you're stuck, make sure you're using the full input \
+         data.  Please wait one minute before trying again.",
+        hint
+    ))
+}
+
+fn route(request: &Request, state: &mut State) -> Response {
+    let not_found = || Response {
+        status: 404,
+        body: "404 Not Found".to_string(),
+    };
+
+    let logged_in = request.cookie.as_deref().and_then(session) == Some(&state.session);
+
+    let segments: Vec<&str> = request.path.trim_start_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", [year, "day", day, "input"]) => {
+            if !logged_in {
+                return Response {
+                    status: 400,
+                    body: "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+                        .to_string(),
+                };
+            }
+
+            match (year.parse(), day.parse()) {
+                (Ok(year), Ok(day)) => match state.inputs.get(&(year, day)) {
+                    Some(input) => Response {
+                        status: 200,
+                        body: input.clone(),
+                    },
+                    None => not_found(),
+                },
+                _ => not_found(),
+            }
+        }
+        ("GET", [year, "day", day]) => match (year.parse(), day.parse()) {
+            (Ok(year), Ok(day)) => match state.pages.get(&(year, day)) {
+                Some(page) => Response {
+                    status: 200,
+                    body: page.clone(),
+                },
+                None => not_found(),
+            },
+            _ => not_found(),
+        },
+        ("POST", [year, "day", day, "answer"]) => {
+            if !logged_in {
+                return Response {
+                    status: 400,
+                    body: "You must be logged in to submit answers.".to_string(),
+                };
+            }
+
+            let form = form(&request.body);
+            let level = form.get("level").and_then(|level| level.parse().ok());
+            match (year.parse(), day.parse(), level, form.get("answer")) {
+                (Ok(year), Ok(day), Some(part), Some(given)) => {
+                    answer(state, (year, day, part), given)
+                }
+                _ => not_found(),
+            }
+        }
+        _ => not_found(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{form, session};
+
+    #[test]
+    pub fn test_session() {
+        assert_eq!(session("session=abc123"), Some("abc123"));
+        assert_eq!(session("foo=bar; session=abc123"), Some("abc123"));
+        assert_eq!(session("foo=bar"), None);
+    }
+
+    #[test]
+    pub fn test_form() {
+        let form = form("level=2&answer=a+b%2Cc");
+        assert_eq!(form["level"], "2");
+        assert_eq!(form["answer"], "a b,c");
+    }
+}
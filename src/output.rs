@@ -0,0 +1,680 @@
+//! Results of running the days, as records that can be written as text, JSON or CSV
+
+use crate::alloc_stats::{self, AllocStats, Bytes};
+use crate::answer::Answer;
+use crate::bench::Nanos;
+use crate::error::StreamError;
+use crate::registry::{DayEntry, Parsed};
+use crate::solution::timed;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+
+    /// The input could not be read or fetched
+    InputError,
+
+    /// The input could not be parsed
+    ParseError,
+    Panicked,
+
+    /// Took longer than the timeout, and was left running in the background
+    TimedOut,
+
+    /// Panicked in a `todo!()` or `unimplemented!()`
+    NotImplemented,
+
+    /// Arithmetic overflowed, which is only caught in builds with overflow checks
+    Overflowed,
+
+    /// The input parsed, but the part has no answer for it
+    NoSolution,
+}
+
+/// Every [Status], in the order they are shown in summaries
+const STATUSES: [Status; 8] = [
+    Status::Ok,
+    Status::InputError,
+    Status::ParseError,
+    Status::Panicked,
+    Status::TimedOut,
+    Status::NotImplemented,
+    Status::Overflowed,
+    Status::NoSolution,
+];
+
+/// The result of one part of one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub status: Status,
+    pub answer: Option<Answer>,
+
+    /// Time spent parsing, shared by both parts of a day, in nanoseconds
+    pub parse_ns: Option<u64>,
+
+    /// Time spent solving the part, in nanoseconds
+    pub solve_ns: Option<u64>,
+
+    /// Where the input was read from
+    pub input: PathBuf,
+
+    /// What went wrong, unless the status is ok
+    pub error: Option<String>,
+
+    /// Allocations made while parsing, if they were counted
+    #[serde(skip)]
+    pub parse_alloc: Option<AllocStats>,
+
+    /// Allocations made while solving the part, if they were counted
+    #[serde(skip)]
+    pub solve_alloc: Option<AllocStats>,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::Ok => "ok",
+            Status::InputError => "input error",
+            Status::ParseError => "parse error",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed out",
+            Status::NotImplemented => "not implemented",
+            Status::Overflowed => "overflowed",
+            Status::NoSolution => "no solution",
+        })
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<String>() {
+        Some(message) => message.clone(),
+        None => match payload.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => "panicked".to_string(),
+        },
+    }
+}
+
+/// Tell `todo!()`, `unimplemented!()` and overflow checks apart from other panics by message
+pub(crate) fn panic_status(message: &str) -> Status {
+    if message.starts_with("not yet implemented") || message.starts_with("not implemented") {
+        Status::NotImplemented
+    } else if message.starts_with("attempt to") && message.ends_with("with overflow") {
+        Status::Overflowed
+    } else {
+        Status::Panicked
+    }
+}
+
+/// What it took to finish a stage
+#[derive(Debug, Clone, Copy)]
+pub struct Cost {
+    pub time: Duration,
+
+    /// The allocations of the stage, if they are being counted, see [alloc_stats]
+    pub alloc: Option<AllocStats>,
+}
+
+/// How a stage went: its result and what it cost, or the status and error it failed with
+pub type Stage<T> = Result<(T, Cost), (Status, String)>;
+
+/// Run `f` on a thread of its own, catching panics and giving up on it after `timeout`
+///
+/// A thread that times out can't be stopped, so it keeps running until the process exits.
+fn isolated<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Stage<T> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let ((result, alloc), time) =
+            timed(|| alloc_stats::measure(|| panic::catch_unwind(AssertUnwindSafe(f))));
+        // the receiver is gone if we timed out, and then nobody wants the result anyway
+        let _ = sender.send(result.map(|out| (out, Cost { time, alloc })));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(done)) => Ok(done),
+        Ok(Err(payload)) => {
+            let message = panic_message(&*payload);
+            Err((panic_status(&message), message))
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            Err((Status::TimedOut, format!("took longer than {:?}", timeout)))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err((Status::Panicked, "exited without an answer".to_string()))
+        }
+    }
+}
+
+/// Parse the input of a day on a thread of its own, giving up after `timeout`
+pub fn parse_day(day: &DayEntry, input: &str, timeout: Duration) -> Stage<Arc<Parsed>> {
+    let parse = day.parse;
+    let input: Arc<str> = input.into();
+    match isolated(timeout, move || parse(&input)) {
+        Ok((Ok(parsed), cost)) => Ok((Arc::new(parsed), cost)),
+        Ok((Err(e), _)) => Err((Status::ParseError, e.to_string())),
+        Err(failed) => Err(failed),
+    }
+}
+
+/// Solve one part of a day on a thread of its own, giving up after `timeout`
+pub fn solve_part(
+    day: &DayEntry,
+    part: u32,
+    parsed: &Arc<Parsed>,
+    timeout: Duration,
+) -> Stage<Answer> {
+    let solve = match part {
+        1 => day.part1,
+        _ => day.part2,
+    };
+    let parsed = Arc::clone(parsed);
+    match isolated(timeout, move || solve(&parsed)) {
+        Ok((Ok(answer), cost)) => Ok((answer, cost)),
+        Ok((Err(e), _)) => Err((Status::NoSolution, e.to_string())),
+        Err(failed) => Err(failed),
+    }
+}
+
+impl Record {
+    /// The record of one part, given what parsing cost and how solving went
+    ///
+    /// If parsing failed, there is no `parse_cost` and `solved` is how parsing failed.
+    pub fn new(
+        day: &DayEntry,
+        part: u32,
+        input: PathBuf,
+        parse_cost: Option<Cost>,
+        solved: Stage<Answer>,
+    ) -> Record {
+        let record = Record {
+            year: day.year,
+            day: day.number,
+            part,
+            status: Status::Ok,
+            answer: None,
+            parse_ns: parse_cost.map(|cost| nanos(cost.time)),
+            solve_ns: None,
+            input,
+            error: None,
+            parse_alloc: parse_cost.and_then(|cost| cost.alloc),
+            solve_alloc: None,
+        };
+
+        match solved {
+            Ok((answer, cost)) => Record {
+                answer: Some(answer),
+                solve_ns: Some(nanos(cost.time)),
+                solve_alloc: cost.alloc,
+                ..record
+            },
+            Err((status, error)) => Record {
+                status,
+                error: Some(error),
+                ..record
+            },
+        }
+    }
+}
+
+/// Records for both parts of a day that failed before either part could run
+pub fn failed_day(day: &DayEntry, path: PathBuf, status: Status, error: String) -> Vec<Record> {
+    [1, 2]
+        .iter()
+        .map(|&part| Record::new(day, part, path.clone(), None, Err((status, error.clone()))))
+        .collect()
+}
+
+/// Run both parts of a day, turning parse errors, panics and timeouts into records
+///
+/// Parsing and each part run on threads of their own and get `timeout` each.
+pub fn run_day(day: &DayEntry, input: &str, path: PathBuf, timeout: Duration) -> Vec<Record> {
+    let parsed = parse_day(day, input, timeout);
+    [1, 2]
+        .iter()
+        .map(|&part| match &parsed {
+            Ok((parsed, parse_cost)) => {
+                let solved = solve_part(day, part, parsed, timeout);
+                Record::new(day, part, path.clone(), Some(*parse_cost), solved)
+            }
+            Err(failed) => Record::new(day, part, path.clone(), None, Err(failed.clone())),
+        })
+        .collect()
+}
+
+/// Solve a day from a stream of its input, in one pass on a thread of its own
+///
+/// `open` is called on that thread, so that a stream that blocks can time out as well. Parts the
+/// day can't solve from a stream get no record, and the time of the whole pass goes to each of
+/// the others, as they are solved together.
+pub fn stream_day(
+    day: &DayEntry,
+    open: impl FnOnce() -> io::Result<Box<dyn BufRead>> + Send + 'static,
+    path: PathBuf,
+    timeout: Duration,
+) -> Vec<Record> {
+    let stream = day.stream.expect("day can't be streamed");
+    let streamed = isolated(timeout, move || stream(&mut *open()?));
+
+    let failed = |(status, error)| failed_day(day, path.clone(), status, error);
+    match streamed {
+        Ok((Ok(streamed), cost)) => [(1, streamed.part1), (2, streamed.part2)]
+            .into_iter()
+            .filter_map(|(part, answer)| {
+                let solved = match answer? {
+                    Ok(answer) => Ok((answer, cost)),
+                    Err(e) => Err((Status::NoSolution, e.to_string())),
+                };
+                Some(Record::new(day, part, path.clone(), None, solved))
+            })
+            .collect(),
+        Ok((Err(e @ StreamError::Io(_)), _)) => failed((Status::InputError, e.to_string())),
+        Ok((Err(e @ StreamError::Parse(_)), _)) => failed((Status::ParseError, e.to_string())),
+        Err(e) => failed(e),
+    }
+}
+
+fn write_text(out: &mut impl Write, records: &[Record]) -> io::Result<()> {
+    let mut previous = None;
+    for record in records {
+        if previous.replace((record.year, record.day)) != Some((record.year, record.day)) {
+            writeln!(out, "day {} ({})", record.day, record.year)?;
+            if let Some(parse) = record.parse_ns {
+                writeln!(out, "parse: {:?}", Duration::from_nanos(parse))?;
+            }
+        }
+
+        match (&record.answer, record.solve_ns, &record.error) {
+            (Some(answer), Some(solve), _) => writeln!(
+                out,
+                "part{}: {} ({:?})",
+                record.part,
+                answer,
+                Duration::from_nanos(solve)
+            )?,
+            (Some(answer), None, _) => writeln!(out, "part{}: {}", record.part, answer)?,
+            (_, _, error) => writeln!(
+                out,
+                "part{}: {}: {}",
+                record.part,
+                record.status,
+                error.as_deref().unwrap_or("")
+            )?,
+        }
+    }
+    Ok(())
+}
+
+/// Write the records in the given format
+///
+/// JSON is written as a single array, and CSV with a header row.
+pub fn write_records(
+    format: Format,
+    out: &mut impl Write,
+    records: &[Record],
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Text => write_text(out, records)?,
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut *out, records)?;
+            writeln!(out)?;
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a table of the status of every part, followed by how many parts ended up in each status
+pub fn write_summary(out: &mut impl Write, records: &[Record]) -> io::Result<()> {
+    let mut days: Vec<(u32, u32)> = records.iter().map(|r| (r.year, r.day)).collect();
+    days.dedup();
+
+    writeln!(out, "{:<9} {:<16} part2", "day", "part1")?;
+    for (year, day) in days {
+        let status = |part| {
+            records
+                .iter()
+                .find(|r| (r.year, r.day, r.part) == (year, day, part))
+                .map_or("-".to_string(), |r| r.status.to_string())
+        };
+        writeln!(out, "{}/{:02}   {:<16} {}", year, day, status(1), status(2))?;
+    }
+
+    let counts: Vec<String> = STATUSES
+        .iter()
+        .map(|&status| {
+            let count = records.iter().filter(|r| r.status == status).count();
+            format!("{} {}", count, status)
+        })
+        .collect();
+    writeln!(out, "{}", counts.join(", "))
+}
+
+/// Write a table of the allocations of every stage that had them counted
+pub fn write_alloc_stats(out: &mut impl Write, records: &[Record]) -> io::Result<()> {
+    writeln!(out, "day       stage   allocations      bytes       peak")?;
+    for record in records {
+        // both parts share the parse, so it's shown once, with part 1
+        let parse = record.parse_alloc.filter(|_| record.part == 1);
+        let part = format!("part{}", record.part);
+        for (stage, stats) in [("parse", parse), (part.as_str(), record.solve_alloc)] {
+            if let Some(stats) = stats {
+                writeln!(
+                    out,
+                    "{}/{:02}   {:<6} {:>12} {:>10} {:>10}",
+                    record.year,
+                    record.day,
+                    stage,
+                    stats.allocations,
+                    Bytes(stats.bytes),
+                    Bytes(stats.peak)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn describe_answer(record: &Record) -> String {
+    match &record.answer {
+        Some(answer) => answer.to_string(),
+        None => record.status.to_string(),
+    }
+}
+
+/// Describe how the records of a re-run differ from the previous run, one line per part
+pub fn diff_records(previous: &[Record], current: &[Record]) -> Vec<String> {
+    current
+        .iter()
+        .map(|record| {
+            let label = format!("day{:02} part{}", record.day, record.part);
+            let answer = describe_answer(record);
+            let before = previous
+                .iter()
+                .find(|p| (p.year, p.day, p.part) == (record.year, record.day, record.part));
+
+            let before = match before {
+                Some(before) => before,
+                None => {
+                    return match record.solve_ns {
+                        Some(solve) => format!("{}: {} ({})", label, answer, Nanos(solve as f64)),
+                        None => format!("{}: {}", label, answer),
+                    }
+                }
+            };
+
+            let answer = match describe_answer(before) {
+                before if before == answer => answer,
+                before => format!("{} -> {} (changed)", before, answer),
+            };
+            match (before.solve_ns, record.solve_ns) {
+                (Some(before), Some(after)) => format!(
+                    "{}: {}, {} -> {} ({:+.1}%)",
+                    label,
+                    answer,
+                    Nanos(before as f64),
+                    Nanos(after as f64),
+                    (after as f64 / before as f64 - 1.0) * 100.0
+                ),
+                (_, Some(after)) => format!("{}: {} ({})", label, answer, Nanos(after as f64)),
+                (_, None) => format!("{}: {}", label, answer),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        diff_records, run_day, stream_day, write_alloc_stats, write_records, write_summary,
+    };
+    use super::{panic_status, Format, Status};
+    use crate::alloc_stats::AllocStats;
+    use crate::day;
+    use crate::registry::{DayEntry, Parsed};
+    use std::io;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn records() -> Vec<super::Record> {
+        let input = include_str!("year2021/day01/examples/example.txt");
+        run_day(
+            day(2021, 1).unwrap(),
+            input,
+            PathBuf::from("day01.txt"),
+            TIMEOUT,
+        )
+    }
+
+    /// A day that panics in part 1 and never finishes part 2
+    static BROKEN: DayEntry = DayEntry {
+        year: 2021,
+        number: 26,
+        name: "day26",
+        parse: |input| match input {
+            "todo" => todo!("impl parse"),
+            _ => Ok(Box::new(()) as Parsed),
+        },
+        part1: |_| panic!("oops"),
+        part2: |_| loop {
+            thread::sleep(Duration::from_secs(1));
+        },
+        generator: |_, _| String::new(),
+        stream: None,
+    };
+
+    #[test]
+    pub fn test_run_day() {
+        let records = records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].status, Status::Ok);
+        assert_eq!(records[0].answer, Some(7.into()));
+        assert_eq!(records[1].answer, Some(5.into()));
+
+        let broken = run_day(day(2021, 1).unwrap(), "x", PathBuf::from("x"), TIMEOUT);
+        assert_eq!(broken[0].status, Status::ParseError);
+        assert!(broken[1].error.is_some());
+
+        // well formed, but without the basins part 2 needs
+        let unsolved = run_day(
+            day(2021, 9).unwrap(),
+            "999\n999\n",
+            PathBuf::from("x"),
+            TIMEOUT,
+        );
+        assert_eq!(unsolved[0].answer, Some(0.into()));
+        assert_eq!(unsolved[1].status, Status::NoSolution);
+    }
+
+    #[test]
+    pub fn test_isolation() {
+        let records = run_day(&BROKEN, "", PathBuf::from("x"), Duration::from_millis(100));
+        assert_eq!(records[0].status, Status::Panicked);
+        assert_eq!(records[0].error.as_deref(), Some("oops"));
+        assert_eq!(records[1].status, Status::TimedOut);
+        assert!(records[1].parse_ns.is_some());
+
+        let records = run_day(&BROKEN, "todo", PathBuf::from("x"), TIMEOUT);
+        assert_eq!(records[0].status, Status::NotImplemented);
+        assert_eq!(
+            records[1].error.as_deref(),
+            Some("not yet implemented: impl parse")
+        );
+    }
+
+    #[test]
+    pub fn test_panic_status() {
+        assert_eq!(
+            panic_status("attempt to multiply with overflow"),
+            Status::Overflowed
+        );
+        assert_eq!(
+            panic_status("not implemented: day 26"),
+            Status::NotImplemented
+        );
+        assert_eq!(panic_status("index out of bounds"), Status::Panicked);
+    }
+
+    #[test]
+    pub fn test_stream_day() {
+        let open = || Ok(Box::new("00100\n11110\n10110\n".as_bytes()) as Box<_>);
+        let records = stream_day(day(2021, 3).unwrap(), open, PathBuf::from("-"), TIMEOUT);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].answer, Some(198.into()));
+        assert!(records[0].solve_ns.is_some());
+
+        // part 2 of day 8 can't decode this, but part 1 still counts
+        let input = "ab ab ab ab ab ab ab ab ab ab | ab ab ab ab\n";
+        let open = move || Ok(Box::new(input.as_bytes()) as Box<_>);
+        let records = stream_day(day(2021, 8).unwrap(), open, PathBuf::from("-"), TIMEOUT);
+        assert_eq!(records[0].answer, Some(4.into()));
+        assert_eq!(records[1].status, Status::NoSolution);
+
+        let open = || Ok(Box::new("1\nx\n".as_bytes()) as Box<_>);
+        let records = stream_day(day(2021, 1).unwrap(), open, PathBuf::from("-"), TIMEOUT);
+        assert_eq!(records[0].status, Status::ParseError);
+
+        let open = || Err(io::Error::new(io::ErrorKind::NotFound, "gone"));
+        let records = stream_day(day(2021, 1).unwrap(), open, PathBuf::from("-"), TIMEOUT);
+        assert_eq!(records[1].status, Status::InputError);
+        assert_eq!(
+            records[1].error.as_deref(),
+            Some("failed to read the input: gone")
+        );
+    }
+
+    #[test]
+    pub fn test_summary() {
+        let mut records = records();
+        records.extend(run_day(&BROKEN, "todo", PathBuf::from("x"), TIMEOUT));
+
+        let mut out = vec![];
+        write_summary(&mut out, &records).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day       part1            part2\n\
+             2021/01   ok               ok\n\
+             2021/26   not implemented  not implemented\n\
+             2 ok, 0 input error, 0 parse error, 0 panicked, 0 timed out, 2 not implemented, \
+             0 overflowed, 0 no solution\n"
+        );
+    }
+
+    #[test]
+    pub fn test_csv() {
+        let mut out = vec![];
+        write_records(Format::Csv, &mut out, &records()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let mut lines = out.lines();
+        assert_eq!(
+            lines.next(),
+            Some("year,day,part,status,answer,parse_ns,solve_ns,input,error")
+        );
+        assert!(lines.next().unwrap().starts_with("2021,1,1,ok,7,"));
+        assert!(lines.next().unwrap().ends_with(",day01.txt,"));
+    }
+
+    #[test]
+    pub fn test_json() {
+        let mut out = vec![];
+        write_records(Format::Json, &mut out, &records()).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json[1]["part"], 2);
+        assert_eq!(json[1]["status"], "ok");
+        assert_eq!(json[1]["answer"], 5);
+    }
+
+    #[test]
+    pub fn test_alloc_stats() {
+        let mut records = records();
+        let stats = |allocations, bytes| AllocStats {
+            allocations,
+            bytes,
+            peak: bytes / 2,
+        };
+        records[0].parse_alloc = Some(stats(3, 4096));
+        records[0].solve_alloc = Some(stats(0, 0));
+        records[1].parse_alloc = Some(stats(3, 4096));
+        records[1].solve_alloc = None;
+
+        let mut out = vec![];
+        write_alloc_stats(&mut out, &records).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day       stage   allocations      bytes       peak\n\
+             2021/01   parse             3     4.0KiB     2.0KiB\n\
+             2021/01   part1             0         0B         0B\n"
+        );
+    }
+
+    #[test]
+    pub fn test_diff() {
+        let previous = records();
+        let mut current = records();
+        current[0].answer = Some(8.into());
+        current[0].solve_ns = previous[0].solve_ns.map(|ns| ns * 2);
+        current[1].answer = None;
+        current[1].solve_ns = None;
+        current[1].status = Status::Panicked;
+
+        let diff = diff_records(&previous, &current);
+        assert!(diff[0].starts_with("day01 part1: 7 -> 8 (changed), "));
+        assert!(diff[0].ends_with(" (+100.0%)"));
+        assert_eq!(diff[1], "day01 part2: 5 -> panicked (changed)");
+        assert!(diff_records(&[], &previous)[0].starts_with("day01 part1: 7 ("));
+    }
+}
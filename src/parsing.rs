@@ -0,0 +1,175 @@
+//! Building blocks for the shapes puzzle inputs come in
+//!
+//! Everything hands out slices of the input it was given, so that a [ParseError] made from them
+//! points at the right line and column. Lines may end in `\r\n`, and whitespace at the end of a
+//! line or of the whole input is ignored.
+
+use crate::error::{ParseError, ParseErrorKind};
+use std::iter;
+use std::ops::RangeBounds;
+use std::str::FromStr;
+
+/// The lines of `text`, without trailing whitespace, and without the blank lines at the end
+pub fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.trim_end().lines().map(str::trim_end)
+}
+
+/// The groups of lines of `text` that are separated by blank lines
+///
+/// Lines within a group keep their line breaks, but the group has no trailing whitespace.
+/// Several blank lines in a row count as one.
+pub fn blocks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    iter::from_fn(move || {
+        // blank lines are skipped whole, as a block can start with indentation
+        while rest.trim_start_matches([' ', '\t', '\r']).starts_with('\n') {
+            rest = &rest[rest.find('\n')? + 1..];
+        }
+        if rest.trim().is_empty() {
+            return None;
+        }
+
+        let len: usize = rest
+            .split_inclusive('\n')
+            .take_while(|line| !line.trim().is_empty())
+            .map(str::len)
+            .sum();
+        let (block, after) = rest.split_at(len);
+        rest = after;
+        Some(block.trim_end())
+    })
+}
+
+/// The whole input of a day, for parsing pieces of it and making errors that point into it
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    day: u32,
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: u32, input: &'a str) -> Self {
+        Source { day, input }
+    }
+
+    /// An error pointing at `token`, which should be a slice of the input
+    pub fn error(&self, token: &str, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(self.day, self.input, token, kind)
+    }
+
+    /// See [lines]
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        lines(self.input)
+    }
+
+    /// See [blocks]
+    pub fn blocks(&self) -> impl Iterator<Item = &'a str> {
+        blocks(self.input)
+    }
+
+    /// Split `s` around the first `delimiter`, like `x | y`
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        delimiter: &'static str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter)
+            .ok_or_else(|| self.error(s, ParseErrorKind::MissingDelimiter(delimiter)))
+    }
+
+    pub fn number<T: FromStr>(&self, token: &'a str) -> Result<T, ParseError> {
+        token
+            .parse()
+            .map_err(|_| self.error(token, ParseErrorKind::InvalidNumber))
+    }
+
+    /// A number that the puzzle only allows within `range`
+    pub fn number_in<T: FromStr + PartialOrd>(
+        &self,
+        token: &'a str,
+        range: impl RangeBounds<T>,
+    ) -> Result<T, ParseError> {
+        match self.number(token)? {
+            n if range.contains(&n) => Ok(n),
+            _ => Err(self.error(token, ParseErrorKind::OutOfRange)),
+        }
+    }
+
+    /// Parse both sides of `delimiter` in `s` with `parse`, like the `3,4` of a position
+    pub fn pair<T>(
+        &self,
+        s: &'a str,
+        delimiter: &'static str,
+        mut parse: impl FnMut(&'a str) -> Result<T, ParseError>,
+    ) -> Result<(T, T), ParseError> {
+        let (a, b) = self.split_once(s, delimiter)?;
+        Ok((parse(a)?, parse(b)?))
+    }
+
+    /// Parse each of the `separator` separated items of `s` with `parse`, like `3,4,3,1,2`
+    ///
+    /// Whitespace around the items is ignored, and so are the line breaks among them.
+    pub fn separated<T, F: FnMut(&'a str) -> Result<T, ParseError>>(
+        &self,
+        s: &'a str,
+        separator: char,
+        parse: F,
+    ) -> impl Iterator<Item = Result<T, ParseError>> + use<'a, T, F> {
+        s.split(separator).map(str::trim).map(parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blocks, lines, Source};
+    use crate::error::ParseErrorKind;
+
+    #[test]
+    pub fn test_lines() {
+        let found: Vec<_> = lines("a \r\nb\r\n\r\nc\t\r\n\r\n  \n").collect();
+        assert_eq!(found, ["a", "b", "", "c"]);
+        assert_eq!(lines(" \n").count(), 0);
+    }
+
+    #[test]
+    pub fn test_blocks() {
+        let input = "\n7,4,9\r\n\r\n 1 2\r\n 3 4\r\n\n\n5 6\n \n";
+        let found: Vec<_> = blocks(input).collect();
+        assert_eq!(found, ["7,4,9", " 1 2\r\n 3 4", "5 6"]);
+
+        let error = Source::new(4, input).error(&found[1][7..], ParseErrorKind::InvalidNumber);
+        assert_eq!((error.line, error.column), (5, 2));
+        assert_eq!(blocks("").count(), 0);
+    }
+
+    #[test]
+    pub fn test_separated() {
+        let input = "3, 4,x\n";
+        let source = Source::new(6, input);
+        let found: Vec<_> = source
+            .separated(input, ',', |n| source.number::<u8>(n))
+            .collect();
+        assert_eq!(found[..2], [Ok(3), Ok(4)]);
+
+        let error = found[2].clone().unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.token.as_str()),
+            (1, 6, "x")
+        );
+    }
+
+    #[test]
+    pub fn test_pair() {
+        let input = "0,9 -> 5,1000";
+        let source = Source::new(5, input);
+        let pos = |s| source.pair(s, ",", |n| source.number_in(n, 0..1000usize));
+        let (start, end) = source.split_once(input, " -> ").unwrap();
+        assert_eq!(pos(start), Ok((0, 9)));
+
+        let error = pos(end).unwrap_err();
+        assert_eq!((error.column, error.kind), (10, ParseErrorKind::OutOfRange));
+
+        let error = source.split_once(input, " | ").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::MissingDelimiter(" | "));
+    }
+}
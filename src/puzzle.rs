@@ -0,0 +1,111 @@
+//! Reading the examples out of a puzzle description page
+//!
+//! The page has one `<article>` per part, and the second only shows up once the first part is
+//! solved. Each part shows its examples in `<pre><code>` blocks, and the answer to the example
+//! is usually the last thing on it that is `<em>`phasized and a single word.
+
+use crate::answer::Answer;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref ARTICLE: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    static ref CODE_BLOCK: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    static ref EMPHASIS: Regex = Regex::new(r"(?s)<em>(.*?)</em>").unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+}
+
+/// What could be read out of a puzzle description
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Description {
+    /// The contents of every `<pre><code>` block, in order. These are the example inputs, though
+    /// a page may also use them for other things.
+    pub examples: Vec<String>,
+
+    /// The answers to the examples of each part that has been revealed, as best as we can tell
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Turn a snippet of HTML into the text it shows
+fn text(html: &str) -> String {
+    TAG.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The last emphasized single word outside of the code blocks
+fn answer(article: &str) -> Option<Answer> {
+    let prose = CODE_BLOCK.replace_all(article, "");
+    EMPHASIS
+        .captures_iter(&prose)
+        .map(|c| text(&c[1]))
+        .filter(|word| !word.is_empty() && !word.contains(char::is_whitespace))
+        .last()
+        .map(|word| Answer::parse(&word))
+}
+
+impl Description {
+    pub fn parse(html: &str) -> Self {
+        let articles: Vec<&str> = ARTICLE
+            .captures_iter(html)
+            .map(|c| c.get(1).unwrap().as_str())
+            .collect();
+
+        Description {
+            examples: CODE_BLOCK
+                .captures_iter(html)
+                .map(|c| text(&c[1]))
+                .collect(),
+            part1: articles.first().and_then(|article| answer(article)),
+            part2: articles.get(1).and_then(|article| answer(article)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Description;
+    use crate::answer::Answer;
+
+    const PAGE: &str = "<main>\n\
+        <article class=\"day-desc\"><h2>--- Day 1: Sonar Sweep ---</h2>\n\
+        <p>For example, suppose you had the following <em>report</em>:</p>\n\
+        <pre><code>199\n<em>200</em>\n208\n</code></pre>\n\
+        <p>In this example, there are <em>7</em> measurements that are larger.</p>\n\
+        <p><em>How many measurements are larger than the previous measurement?</em></p>\n\
+        </article>\n\
+        <p>Your puzzle answer was <code>1292</code>.</p>\n\
+        <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\n\
+        <pre><code>a -&gt; b &amp;&amp; c\n</code></pre>\n\
+        <p>The final score would be <code>148 * 13 = <em>1924</em></code>.</p>\n\
+        </article>\n\
+        </main>";
+
+    #[test]
+    pub fn test_description() {
+        let description = Description::parse(PAGE);
+        assert_eq!(
+            description.examples,
+            vec!["199\n200\n208\n", "a -> b && c\n"]
+        );
+        assert_eq!(description.part1, Some(Answer::from(7)));
+        assert_eq!(description.part2, Some(Answer::from(1924)));
+    }
+
+    #[test]
+    pub fn test_unsolved() {
+        let part1 = PAGE.split("<p>Your puzzle answer").next().unwrap();
+        let description = Description::parse(part1);
+        assert_eq!(description.examples.len(), 1);
+        assert_eq!(description.part2, None);
+
+        assert_eq!(
+            Description::parse("<p>no examples</p>"),
+            Description::default()
+        );
+    }
+}
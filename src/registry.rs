@@ -0,0 +1,289 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, SolveError, StreamError};
+use crate::solution::{timed, Generator, Report, Solution, Streamed, Streaming};
+use crate::*;
+use fastrand::Rng;
+use std::any::Any;
+use std::error::Error;
+use std::io::BufRead;
+use std::path::PathBuf;
+
+/// The parsed input of some day, with its concrete type erased
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// Solve a day from a stream of its input, see [Streaming]
+pub type Stream = fn(&mut dyn BufRead) -> Result<Streamed, StreamError>;
+
+/// A day in the registry, with type-erased function pointers to its [Solution]
+pub struct DayEntry {
+    pub year: u32,
+    pub number: u32,
+
+    /// Name of the module of the day, e.g. `day01`
+    pub name: &'static str,
+
+    pub parse: fn(&str) -> Result<Parsed, ParseError>,
+
+    /// Solve part 1. Panics if the [Parsed] input didn't come from this day's `parse`.
+    pub part1: fn(&Parsed) -> Result<Answer, SolveError>,
+
+    /// Solve part 2. Panics if the [Parsed] input didn't come from this day's `parse`.
+    pub part2: fn(&Parsed) -> Result<Answer, SolveError>,
+
+    /// Generate a random input, see [Generator]
+    pub generator: fn(&mut Rng, usize) -> String,
+
+    /// Solve the day from a stream of its input, for the days that are [Streaming]
+    pub stream: Option<Stream>,
+}
+
+fn parse<S: Solution>(input: &str) -> Result<Parsed, ParseError>
+where
+    S::Input: Send + Sync + 'static,
+{
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn downcast<S: Solution>(parsed: &Parsed) -> &S::Input
+where
+    S::Input: 'static,
+{
+    parsed
+        .downcast_ref()
+        .expect("parsed input belongs to another day")
+}
+
+fn part1<S: Solution>(parsed: &Parsed) -> Result<Answer, SolveError>
+where
+    S::Input: 'static,
+{
+    S::part1(downcast::<S>(parsed))
+}
+
+fn part2<S: Solution>(parsed: &Parsed) -> Result<Answer, SolveError>
+where
+    S::Input: 'static,
+{
+    S::part2(downcast::<S>(parsed))
+}
+
+impl DayEntry {
+    const fn new<S: Solution + Generator>(year: u32, number: u32, name: &'static str) -> Self
+    where
+        S::Input: Send + Sync + 'static,
+    {
+        DayEntry {
+            year,
+            number,
+            name,
+            parse: parse::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+            generator: S::generate,
+            stream: None,
+        }
+    }
+
+    const fn streaming<S: Streaming>(self) -> Self {
+        DayEntry {
+            stream: Some(S::stream),
+            ..self
+        }
+    }
+
+    /// Path of the module of the day, e.g. `year2021::day01`
+    pub fn module(&self) -> String {
+        format!("{}::{}", year_module(self.year), self.name)
+    }
+
+    /// Directory of the day within `src`, e.g. `year2021/day01`
+    pub fn dir(&self) -> PathBuf {
+        PathBuf::from(year_module(self.year)).join(self.name)
+    }
+
+    /// Generate a random input of the given size, always the same one for the same seed
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generator)(&mut Rng::with_seed(seed), size)
+    }
+
+    /// Parse the input once and solve both parts
+    pub fn run(&self, input: &str) -> Result<Report, Box<dyn Error>> {
+        let (parsed, parse_time) = timed(|| (self.parse)(input));
+        let parsed = parsed?;
+
+        let (part1, part1_time) = timed(|| (self.part1)(&parsed));
+        let (part2, part2_time) = timed(|| (self.part2)(&parsed));
+        Ok(Report {
+            parse_time,
+            part1: (part1?, part1_time),
+            part2: (part2?, part2_time),
+        })
+    }
+
+    /// Feed arbitrary bytes through the parser and, if they parse, both parts
+    ///
+    /// Input that isn't UTF-8, doesn't parse or has no solution is fine. Any other problem
+    /// panics, which is what the fuzz targets look for.
+    pub fn fuzz(&self, data: &[u8]) {
+        let input = match std::str::from_utf8(data) {
+            Ok(input) => input,
+            Err(_) => return,
+        };
+
+        if let Ok(parsed) = (self.parse)(input) {
+            let _ = (self.part1)(&parsed);
+            let _ = (self.part2)(&parsed);
+        }
+    }
+}
+
+macro_rules! registry {
+    ($(
+        ($year:literal, $number:literal) => $module:ident::$day:ident::$solution:ident
+        $(+ $streaming:ident)?
+    ),* $(,)?) => {
+        static DAYS: &[DayEntry] = &[
+            $(DayEntry::new::<$module::$day::$solution>($year, $number, stringify!($day))
+                $(.$streaming::<$module::$day::$solution>())?,)*
+        ];
+    };
+}
+
+registry! {
+    (2021, 1) => year2021::day01::Day01 + streaming,
+    (2021, 2) => year2021::day02::Day02 + streaming,
+    (2021, 3) => year2021::day03::Day03 + streaming,
+    (2021, 4) => year2021::day04::Day04,
+    (2021, 5) => year2021::day05::Day05,
+    (2021, 6) => year2021::day06::Day06 + streaming,
+    (2021, 7) => year2021::day07::Day07,
+    (2021, 8) => year2021::day08::Day08 + streaming,
+    (2021, 9) => year2021::day09::Day09,
+}
+
+/// Name of the module that holds the days of a year, e.g. `year2021`
+pub fn year_module(year: u32) -> String {
+    format!("year{}", year)
+}
+
+/// All implemented days, ordered by year and then day number
+pub fn days() -> &'static [DayEntry] {
+    DAYS
+}
+
+/// The implemented days of one year
+pub fn days_of(year: u32) -> impl Iterator<Item = &'static DayEntry> {
+    DAYS.iter().filter(move |day| day.year == year)
+}
+
+/// Look up a day by its year and number
+pub fn day(year: u32, number: u32) -> Option<&'static DayEntry> {
+    days_of(year).find(|day| day.number == number)
+}
+
+/// The most recent year with any days implemented
+pub fn latest_year() -> Option<u32> {
+    DAYS.last().map(|day| day.year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{day, days, days_of, latest_year};
+    use crate::testing::{mangle, skip_todo};
+    use fastrand::Rng;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    pub fn test_registry_order() {
+        let keys: Vec<(u32, u32)> = days().iter().map(|day| (day.year, day.number)).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", keys);
+
+        for entry in days() {
+            assert!((1..=25).contains(&entry.number));
+            assert_eq!(entry.name, format!("day{:02}", entry.number));
+        }
+    }
+
+    #[test]
+    pub fn test_run_examples() {
+        for entry in days() {
+            let examples = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src")
+                .join(entry.dir())
+                .join("examples");
+            let inputs: Vec<_> = fs::read_dir(examples)
+                .unwrap()
+                .map(|file| file.unwrap().path())
+                .filter(|path| path.extension() == Some("txt".as_ref()))
+                .collect();
+            assert!(!inputs.is_empty(), "{} has no examples", entry.module());
+
+            skip_todo(&entry.module(), || {
+                for path in inputs {
+                    let input = fs::read_to_string(&path).unwrap();
+                    assert!(entry.run(&input).is_ok(), "{} failed", path.display());
+                }
+            });
+        }
+    }
+
+    #[test]
+    pub fn test_generated_inputs() {
+        for entry in days() {
+            skip_todo(&entry.module(), || {
+                for seed in 0..5 {
+                    let input = entry.generate(seed, 100);
+                    assert_eq!(input, entry.generate(seed, 100));
+                    let ok = entry.run(&input).is_ok();
+                    assert!(ok, "{} failed on seed {}", entry.module(), seed);
+                }
+            });
+        }
+    }
+
+    #[test]
+    pub fn test_mangled_inputs() {
+        let edge_cases = [
+            "",
+            "\n",
+            "\n\n",
+            "0",
+            "-1",
+            "99999999999999999999",
+            ",",
+            "0,0 -> 0,0",
+        ];
+
+        for entry in days() {
+            skip_todo(&entry.module(), || {
+                for input in edge_cases {
+                    entry.fuzz(input.as_bytes());
+                }
+
+                for seed in 0..20 {
+                    let mut rng = Rng::with_seed(seed);
+                    let input = (entry.generator)(&mut rng, 10);
+                    entry.fuzz(&mangle(&mut rng, &input));
+                }
+            });
+        }
+    }
+
+    #[test]
+    pub fn test_lookup() {
+        let day01 = day(2021, 1).unwrap();
+        let parsed = (day01.parse)(include_str!("year2021/day01/examples/example.txt")).unwrap();
+        assert_eq!((day01.part1)(&parsed), Ok(7.into()));
+        assert_eq!(day01.module(), "year2021::day01");
+        assert!(day(2021, 0).is_none());
+        // there was no Advent of Code before 2015
+        assert!(day(2014, 1).is_none());
+
+        // days are added as they are solved, so only check what the first ones make true
+        assert!(latest_year() >= Some(2021));
+        assert!(days_of(2021).all(|day| day.year == 2021));
+        assert_eq!(days_of(2021).next().map(|day| day.number), Some(1));
+        assert_eq!(days_of(2014).count(), 0);
+    }
+}
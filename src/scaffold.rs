@@ -0,0 +1,337 @@
+//! Creating a new day from the template in `src/_dayxx`
+//!
+//! The days of a year live in `src/yearYYYY`. Besides the day's own directory, the day is declared
+//! in the module of its year, which is created if it's the first day of the year, and added to
+//! the registry in `src/registry.rs`, so it can be run straight away.
+//!
+//! The examples of a day are in its `examples` directory, each as a `NAME.txt` input with the
+//! expected answers to it in `NAME.toml`, which looks like:
+//!
+//! ```toml
+//! part1 = 7
+//! part2 = 5
+//! ```
+//!
+//! Every part of every example is tested, see `src/examples.rs`.
+
+use crate::known_answers::Parts;
+use crate::puzzle::Description;
+use crate::registry::year_module;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day already has a directory
+    Exists(PathBuf),
+
+    /// The file doesn't look the way it should, so the day couldn't be added to it
+    Unrecognized(PathBuf),
+
+    Io(PathBuf, io::Error),
+}
+
+/// The example input of a day and the expected answers to it
+#[derive(Debug, Clone, Default)]
+pub struct Example {
+    pub input: String,
+    pub answers: Parts,
+}
+
+impl Example {
+    /// Take the example with the given index from a puzzle description, if it has that many
+    pub fn from_description(description: &Description, index: usize) -> Option<Example> {
+        Some(Example {
+            input: description.examples.get(index)?.clone(),
+            answers: Parts {
+                part1: description.part1.clone(),
+                part2: description.part2.clone(),
+            },
+        })
+    }
+}
+
+/// Fill in the template for a day
+fn instantiate(template: &str, day: u32) -> String {
+    template
+        .replace("DayXX", &format!("Day{:02}", day))
+        .replace("const DAY: u32 = 0;", &format!("const DAY: u32 = {};", day))
+}
+
+/// Insert `line` among the lines for which `is_entry` is true, keeping them ordered by `key`
+///
+/// None if there are no such lines to put it next to.
+fn insert_line<K: Ord>(
+    text: &str,
+    line: &str,
+    is_entry: impl Fn(&str) -> bool,
+    key: impl Fn(&str) -> K,
+) -> Option<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let entries: Vec<usize> = (0..lines.len()).filter(|&i| is_entry(lines[i])).collect();
+
+    let at = entries
+        .iter()
+        .copied()
+        .find(|&i| key(lines[i]) > key(line))
+        .unwrap_or(*entries.last()? + 1);
+    lines.insert(at, line);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Declare `module`, e.g. `day01`, among the modules with the same prefix in `parent`
+fn add_module(parent: &str, module: &str) -> Option<String> {
+    let prefix = format!(
+        "pub mod {}",
+        module.trim_end_matches(|c: char| c.is_ascii_digit())
+    );
+    let is_sibling = |line: &str| line.starts_with(&prefix);
+    insert_line(
+        parent,
+        &format!("pub mod {};", module),
+        is_sibling,
+        str::to_string,
+    )
+}
+
+/// Add a day to the `registry!` in `registry.rs`
+fn add_to_registry(registry: &str, year: u32, day: u32) -> Option<String> {
+    let key = |line: &str| -> Option<(u32, u32)> {
+        let key = line.trim().split_once(" => ")?.0;
+        let (year, day) = key.strip_prefix('(')?.strip_suffix(')')?.split_once(", ")?;
+        Some((year.parse().ok()?, day.parse().ok()?))
+    };
+    let start = registry.find("\nregistry! {\n")?;
+    let end = start + registry[start..].find("\n}\n")?;
+
+    let entry = format!(
+        "    ({}, {}) => {}::day{:02}::Day{:02},",
+        year,
+        day,
+        year_module(year),
+        day,
+        day
+    );
+    let entries = insert_line(
+        &registry[start..end],
+        &entry,
+        |line| key(line).is_some(),
+        key,
+    )?;
+    Some(format!(
+        "{}{}{}",
+        &registry[..start],
+        entries.trim_end(),
+        &registry[end..]
+    ))
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|e| ScaffoldError::Io(path.to_owned(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|e| ScaffoldError::Io(path.to_owned(), e))
+}
+
+/// Write an example to the `examples` of the day in `dir`, replacing any with the same name
+///
+/// Returns the path of the example input.
+pub fn write_example(dir: &Path, name: &str, example: &Example) -> Result<PathBuf, ScaffoldError> {
+    let examples = dir.join("examples");
+    fs::create_dir_all(&examples).map_err(|e| ScaffoldError::Io(examples.clone(), e))?;
+
+    let input = examples.join(format!("{}.txt", name));
+    let answers = toml::to_string(&example.answers).expect("answers are always valid TOML");
+    write(&input, &example.input)?;
+    write(&input.with_extension("toml"), &answers)?;
+    Ok(input)
+}
+
+/// Create `src/yearYYYY/dayNN` in the crate at `root` and register the day
+///
+/// Returns the directory of the new day.
+pub fn new_day(
+    root: &Path,
+    year: u32,
+    day: u32,
+    example: &Example,
+) -> Result<PathBuf, ScaffoldError> {
+    let src = root.join("src");
+    let year_dir = src.join(year_module(year));
+    let dir = year_dir.join(format!("day{:02}", day));
+    if dir.exists() {
+        return Err(ScaffoldError::Exists(dir));
+    }
+
+    // check everything before writing anything, so a failure leaves the crate as it was
+    let day_module = format!("day{:02}", day);
+    let year_path = year_dir.join("mod.rs");
+    let new_year = !year_path.exists();
+    let (parent_path, parent) = match new_year {
+        false => (
+            year_path.clone(),
+            add_module(&read(&year_path)?, &day_module),
+        ),
+        true => {
+            let lib_path = src.join("lib.rs");
+            (
+                lib_path.clone(),
+                add_module(&read(&lib_path)?, &year_module(year)),
+            )
+        }
+    };
+    let parent = parent.ok_or_else(|| ScaffoldError::Unrecognized(parent_path.clone()))?;
+    let registry_path = src.join("registry.rs");
+    let registry = add_to_registry(&read(&registry_path)?, year, day)
+        .ok_or_else(|| ScaffoldError::Unrecognized(registry_path.clone()))?;
+    let source = instantiate(&read(&src.join("_dayxx").join("mod.rs"))?, day);
+
+    fs::create_dir_all(&dir).map_err(|e| ScaffoldError::Io(dir.clone(), e))?;
+    write(&dir.join("mod.rs"), &source)?;
+    write_example(&dir, "example", example)?;
+    if new_year {
+        let module = format!("//! Advent of Code {}\n\npub mod {};\n", year, day_module);
+        write(&year_path, &module)?;
+    }
+    write(&parent_path, &parent)?;
+    write(&registry_path, &registry)?;
+
+    Ok(dir)
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Unrecognized(path) => {
+                write!(f, "don't know where to add the day in {}", path.display())
+            }
+            ScaffoldError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for ScaffoldError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_module, add_to_registry, instantiate, new_day, Example, ScaffoldError};
+    use crate::known_answers::Parts;
+    use std::fs;
+
+    const TEMPLATE: &str = include_str!("_dayxx/mod.rs");
+
+    // stand-ins for the files a day is added to, as the real ones change with every new day
+    const LIB: &str = "pub mod answer;\npub mod registry;\n\npub mod year2021;\n";
+    const YEAR: &str = "//! Advent of Code 2021\n\npub mod day01;\npub mod day09;\n";
+    const REGISTRY: &str = "use crate::*;\n\n\
+        registry! {\n    \
+            (2021, 1) => year2021::day01::Day01 + streaming,\n    \
+            (2021, 9) => year2021::day09::Day09,\n\
+        }\n\n\
+        pub fn days() {}\n";
+
+    #[test]
+    pub fn test_instantiate() {
+        let source = instantiate(TEMPLATE, 12);
+        assert!(source.contains("pub struct Day12;"));
+        assert!(source.contains("const DAY: u32 = 12;"));
+        assert!(!source.contains("XX"));
+    }
+
+    #[test]
+    pub fn test_add_module() {
+        let lib = "pub mod answer;\n\npub mod day01;\npub mod day03;\n\npub use x;\n";
+        assert_eq!(
+            add_module(lib, "day02").unwrap(),
+            "pub mod answer;\n\npub mod day01;\npub mod day02;\npub mod day03;\n\npub use x;\n"
+        );
+        assert!(add_module(lib, "day04")
+            .unwrap()
+            .contains("pub mod day03;\npub mod day04;\n\n"));
+        assert_eq!(add_module("pub mod answer;\n", "day01"), None);
+
+        let added = add_module(LIB, "year2022").unwrap();
+        assert!(added.contains("pub mod year2021;\npub mod year2022;\n"));
+    }
+
+    #[test]
+    pub fn test_add_to_registry() {
+        let entry = "    (2021, 10) => year2021::day10::Day10,\n";
+        let added = add_to_registry(REGISTRY, 2021, 10).unwrap();
+        assert!(added.contains(&format!("year2021::day09::Day09,\n{}}}\n", entry)));
+        assert_eq!(added.len(), REGISTRY.len() + entry.len());
+
+        let entry = "    (2021, 5) => year2021::day05::Day05,\n";
+        let added = add_to_registry(REGISTRY, 2021, 5).unwrap();
+        assert!(added.contains(&format!("Day01 + streaming,\n{}    (2021, 9)", entry)));
+
+        let added = add_to_registry(REGISTRY, 2015, 1).unwrap();
+        assert!(added.contains("{\n    (2015, 1) => year2015::day01::Day01,\n    (2021, 1) =>"));
+    }
+
+    fn crate_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("_dayxx")).unwrap();
+        fs::create_dir_all(src.join("year2021")).unwrap();
+        fs::write(src.join("_dayxx").join("mod.rs"), TEMPLATE).unwrap();
+        fs::write(src.join("lib.rs"), LIB).unwrap();
+        fs::write(src.join("registry.rs"), REGISTRY).unwrap();
+        fs::write(src.join("year2021").join("mod.rs"), YEAR).unwrap();
+        dir
+    }
+
+    #[test]
+    pub fn test_new_day() {
+        let dir = crate_dir();
+        let src = dir.path().join("src");
+
+        let example = Example {
+            input: "1\n2\n".to_string(),
+            answers: Parts {
+                part1: Some(7.into()),
+                part2: None,
+            },
+        };
+        let day_dir = new_day(dir.path(), 2021, 10, &example).unwrap();
+        assert_eq!(day_dir, src.join("year2021").join("day10"));
+        let examples = day_dir.join("examples");
+        assert_eq!(
+            fs::read_to_string(examples.join("example.txt")).unwrap(),
+            "1\n2\n"
+        );
+        let answers = fs::read_to_string(examples.join("example.toml")).unwrap();
+        assert_eq!(answers, "part1 = 7\n");
+        assert!(fs::read_to_string(day_dir.join("mod.rs"))
+            .unwrap()
+            .contains("Day10"));
+        let year = fs::read_to_string(src.join("year2021").join("mod.rs")).unwrap();
+        assert!(year.contains("pub mod day09;\npub mod day10;\n"));
+        assert_eq!(fs::read_to_string(src.join("lib.rs")).unwrap(), LIB);
+
+        let error = new_day(dir.path(), 2021, 10, &example).unwrap_err();
+        assert!(matches!(error, ScaffoldError::Exists(_)));
+    }
+
+    #[test]
+    pub fn test_new_year() {
+        let dir = crate_dir();
+        let src = dir.path().join("src");
+
+        let day_dir = new_day(dir.path(), 2022, 1, &Example::default()).unwrap();
+        assert_eq!(day_dir, src.join("year2022").join("day01"));
+        let year = fs::read_to_string(src.join("year2022").join("mod.rs")).unwrap();
+        assert_eq!(year, "//! Advent of Code 2022\n\npub mod day01;\n");
+        assert!(fs::read_to_string(src.join("lib.rs"))
+            .unwrap()
+            .contains("pub mod year2022;"));
+        let registry = fs::read_to_string(src.join("registry.rs")).unwrap();
+        assert!(registry.contains("    (2022, 1) => year2022::day01::Day01,\n}\n"));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, SolveError, StreamError};
+use fastrand::Rng;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+/// The solution to a single day of Advent of Code
+///
+/// The input is parsed once by [Solution::parse] and then shared by both parts. Parsing only
+/// checks that the input is well formed, and a part that finds no answer in it says so with a
+/// [SolveError].
+pub trait Solution {
+    /// The parsed puzzle input
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError>;
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError>;
+}
+
+/// Produces random, valid puzzle inputs for a [Solution], for stress tests and benchmarks
+pub trait Generator {
+    /// Generate an input. What `size` counts depends on the day, e.g. lines or bingo boards.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// A [Solution] that can also be solved while reading its input a piece at a time
+///
+/// Memory use doesn't grow with the input, so inputs larger than memory can be solved. The input
+/// is only checked as far as the parts that are solved need it to be.
+pub trait Streaming: Solution {
+    /// Solve the parts that can be solved in one pass over `input`, leaving the others `None`
+    fn stream(input: &mut dyn BufRead) -> Result<Streamed, StreamError>;
+}
+
+/// What one pass of [Streaming::stream] got for each part, `None` for the parts it doesn't solve
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Streamed {
+    pub part1: Option<Result<Answer, SolveError>>,
+    pub part2: Option<Result<Answer, SolveError>>,
+}
+
+/// Call `f` with each line of `input`, moving the errors it returns to where the line is
+///
+/// The lines are the same as [parsing::lines](crate::parsing::lines) gives, so blank lines are
+/// only passed on once there is another line after them. Returns the number of lines passed on.
+/// Only one line is kept in memory at a time.
+pub fn for_each_line(
+    input: &mut dyn BufRead,
+    mut f: impl FnMut(&str) -> Result<(), ParseError>,
+) -> Result<usize, StreamError> {
+    let mut buf = String::new();
+    let (mut lines, mut blank) = (0, 0);
+    loop {
+        buf.clear();
+        if input.read_line(&mut buf)? == 0 {
+            return Ok(lines);
+        }
+
+        let line = buf.trim_end();
+        if line.is_empty() {
+            blank += 1;
+            continue;
+        }
+        for _ in 0..blank {
+            lines += 1;
+            f("").map_err(|e| e.shifted(lines, 1))?;
+        }
+        blank = 0;
+
+        lines += 1;
+        f(line).map_err(|e| e.shifted(lines, 1))?;
+    }
+}
+
+/// The answers of a [Solution], along with how long each stage took
+pub struct Report {
+    pub parse_time: Duration,
+    pub part1: (Answer, Duration),
+    pub part2: (Answer, Duration),
+}
+
+pub(crate) fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let out = f();
+    (out, start.elapsed())
+}
+
+/// Parse the input once and solve both parts of a [Solution]
+pub fn run<S: Solution>(input: &str) -> Result<Report, Box<dyn Error>> {
+    let (parsed, parse_time) = timed(|| S::parse(input));
+    let parsed = parsed?;
+
+    let (part1, part1_time) = timed(|| S::part1(&parsed));
+    let (part2, part2_time) = timed(|| S::part2(&parsed));
+    Ok(Report {
+        parse_time,
+        part1: (part1?, part1_time),
+        part2: (part2?, part2_time),
+    })
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "parse: {:?}", self.parse_time)?;
+        writeln!(f, "part1: {} ({:?})", self.part1.0, self.part1.1)?;
+        write!(f, "part2: {} ({:?})", self.part2.0, self.part2.1)
+    }
+}
+
+/// Expose a [Solution] as free `parse`, `part1` and `part2` functions that take the raw input.
+macro_rules! solution {
+    ($solution:ty) => {
+        pub fn parse(
+            input: &str,
+        ) -> Result<<$solution as $crate::solution::Solution>::Input, $crate::error::ParseError> {
+            <$solution as $crate::solution::Solution>::parse(input)
+        }
+
+        pub fn part1(input: &str) -> Result<$crate::answer::Answer, Box<dyn std::error::Error>> {
+            Ok(<$solution as $crate::solution::Solution>::part1(&parse(
+                input,
+            )?)?)
+        }
+
+        pub fn part2(input: &str) -> Result<$crate::answer::Answer, Box<dyn std::error::Error>> {
+            Ok(<$solution as $crate::solution::Solution>::part2(&parse(
+                input,
+            )?)?)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{for_each_line, run};
+    use crate::error::{ParseError, ParseErrorKind, StreamError};
+    use crate::year2021::day01::Day01;
+
+    #[test]
+    pub fn test_run() {
+        let input = include_str!("year2021/day01/examples/example.txt");
+        let report = run::<Day01>(input).unwrap();
+        assert_eq!(report.part1.0, 7.into());
+        assert_eq!(report.part2.0, 5.into());
+
+        assert!(run::<Day01>("1\nx\n").is_err());
+    }
+
+    #[test]
+    pub fn test_for_each_line() {
+        let mut lines = vec![];
+        let count = for_each_line(&mut "a \r\nb\n\nc\n\n \n".as_bytes(), |line| {
+            lines.push(line.to_string());
+            Ok(())
+        });
+        assert_eq!(count.unwrap(), 4);
+        assert_eq!(lines, ["a", "b", "", "c"]);
+
+        let result = for_each_line(&mut "1\n2\n3x\n".as_bytes(), |line| match line.find('x') {
+            Some(i) => Err(ParseError::new(
+                1,
+                line,
+                &line[i..],
+                ParseErrorKind::InvalidNumber,
+            )),
+            None => Ok(()),
+        });
+        match result {
+            Err(StreamError::Parse(error)) => assert_eq!((error.line, error.column), (3, 2)),
+            _ => panic!("expected a parse error, got {:?}", result),
+        }
+    }
+}
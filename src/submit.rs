@@ -0,0 +1,411 @@
+//! Submitting answers, and keeping track of what has already been submitted
+//!
+//! Every attempt is recorded in a [SubmitLog], so that an answer that is already known to be
+//! wrong is never sent again, and so that the cooldown the server imposes is honoured before
+//! even making a request.
+
+use crate::fetch::{Client, FetchError};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// What the server said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+
+    /// Wrong, without a hint in which direction
+    Incorrect,
+}
+
+/// The page the server responds with after submitting an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    /// The answer was checked. A wrong answer comes with a cooldown before the next attempt.
+    Checked {
+        outcome: Outcome,
+        wait: Option<Duration>,
+    },
+
+    /// The answer was not checked since the cooldown of a previous attempt hasn't passed
+    TooSoon(Duration),
+
+    /// The part is already solved, or part 1 isn't solved yet
+    WrongLevel,
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// This part has already been solved with the given answer
+    AlreadySolved(String),
+
+    /// This exact answer has already been submitted
+    AlreadyTried(Outcome),
+
+    /// The server won't accept an answer until the cooldown has passed
+    Cooldown(Duration),
+
+    /// The server responded with a page we don't understand
+    UnknownResponse(String),
+
+    Fetch(FetchError),
+    Io(PathBuf, io::Error),
+    Toml(PathBuf, String),
+}
+
+lazy_static! {
+    static ref ARTICLE: Regex = Regex::new(r"(?s)<article>(.*?)</article>").unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref LEFT_TO_WAIT: Regex =
+        Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    static ref PLEASE_WAIT: Regex = Regex::new(r"(?i)please wait (\w+) minutes?").unwrap();
+}
+
+impl Response {
+    /// Parse the page returned when submitting an answer
+    pub fn parse(page: &str) -> Option<Response> {
+        let article = ARTICLE
+            .captures(page)
+            .map_or(page, |c| c.get(1).unwrap().as_str());
+        let text = TAG.replace_all(article, "");
+
+        if text.contains("That's the right answer") {
+            return Some(Response::Checked {
+                outcome: Outcome::Correct,
+                wait: None,
+            });
+        }
+
+        if text.contains("That's not the right answer") {
+            let outcome = if text.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if text.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Incorrect
+            };
+
+            let wait = PLEASE_WAIT.captures(&text).and_then(|c| {
+                let minutes = match &c[1] {
+                    "one" => 1,
+                    n => n.parse().ok()?,
+                };
+                Some(Duration::from_secs(minutes * 60))
+            });
+
+            return Some(Response::Checked { outcome, wait });
+        }
+
+        if text.contains("You gave an answer too recently") {
+            let wait = LEFT_TO_WAIT.captures(&text).map_or(0, |c| {
+                let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+                let seconds: u64 = c[2].parse().unwrap();
+                minutes * 60 + seconds
+            });
+            return Some(Response::TooSoon(Duration::from_secs(wait)));
+        }
+
+        if text.contains("You don't seem to be solving the right level") {
+            return Some(Response::WrongLevel);
+        }
+
+        None
+    }
+}
+
+/// A previously submitted answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+
+    /// When the attempt was made, in seconds since the unix epoch
+    pub time: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct LogFile {
+    /// No answers are accepted before this time, in seconds since the unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait_until: Option<u64>,
+
+    #[serde(default, rename = "attempt")]
+    attempts: Vec<Attempt>,
+}
+
+/// Every answer submitted so far, stored on disk as TOML
+pub struct SubmitLog {
+    path: PathBuf,
+    log: LogFile,
+}
+
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+impl SubmitLog {
+    /// Read the log at `path`, or start an empty one if it doesn't exist
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, SubmitError> {
+        let path = path.into();
+        let log = match fs::read_to_string(&path) {
+            Ok(text) => {
+                toml::from_str(&text).map_err(|e| SubmitError::Toml(path.clone(), e.to_string()))?
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => LogFile::default(),
+            Err(e) => return Err(SubmitError::Io(path, e)),
+        };
+
+        Ok(SubmitLog { path, log })
+    }
+
+    pub fn save(&self) -> Result<(), SubmitError> {
+        let text = toml::to_string(&self.log)
+            .map_err(|e| SubmitError::Toml(self.path.clone(), e.to_string()))?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| SubmitError::Io(dir.to_owned(), e))?;
+        }
+        fs::write(&self.path, text).map_err(|e| SubmitError::Io(self.path.clone(), e))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All attempts for one part of a day, oldest first
+    pub fn attempts(&self, year: u32, day: u32, part: u32) -> impl Iterator<Item = &Attempt> {
+        self.log
+            .attempts
+            .iter()
+            .filter(move |a| (a.year, a.day, a.part) == (year, day, part))
+    }
+
+    /// How long is left of the cooldown at time `now`, if anything
+    pub fn cooldown(&self, now: SystemTime) -> Option<Duration> {
+        let now = unix_time(now);
+        self.log
+            .wait_until
+            .filter(|&until| until > now)
+            .map(|until| Duration::from_secs(until - now))
+    }
+
+    /// Check whether `answer` is worth submitting at time `now`
+    pub fn check(
+        &self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+        now: SystemTime,
+    ) -> Result<(), SubmitError> {
+        if let Some(correct) = self
+            .attempts(year, day, part)
+            .find(|a| a.outcome == Outcome::Correct)
+        {
+            return Err(SubmitError::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(tried) = self.attempts(year, day, part).find(|a| a.answer == answer) {
+            return Err(SubmitError::AlreadyTried(tried.outcome));
+        }
+
+        match self.cooldown(now) {
+            Some(wait) => Err(SubmitError::Cooldown(wait)),
+            None => Ok(()),
+        }
+    }
+
+    /// Record the response to submitting `answer` at time `now`
+    pub fn record(
+        &mut self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+        response: Response,
+        now: SystemTime,
+    ) {
+        let time = unix_time(now);
+        match response {
+            Response::Checked { outcome, wait } => {
+                self.log.attempts.push(Attempt {
+                    year,
+                    day,
+                    part,
+                    answer: answer.to_string(),
+                    outcome,
+                    time,
+                });
+                self.log.wait_until = wait.map(|wait| time + wait.as_secs());
+            }
+            Response::TooSoon(wait) => self.log.wait_until = Some(time + wait.as_secs()),
+            Response::WrongLevel => {}
+        }
+    }
+}
+
+/// Submit an answer, unless the log says it's pointless, and record the response in the log
+///
+/// The log is saved as soon as the server has responded.
+pub fn submit(
+    client: &Client,
+    log: &mut SubmitLog,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Response, SubmitError> {
+    log.check(year, day, part, answer, SystemTime::now())?;
+
+    let page = client
+        .answer(year, day, part, answer)
+        .map_err(SubmitError::Fetch)?;
+    let response = Response::parse(&page).ok_or(SubmitError::UnknownResponse(page))?;
+
+    log.record(year, day, part, answer, response, SystemTime::now());
+    log.save()?;
+    Ok(response)
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+        }
+    }
+}
+
+impl Display for Response {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Checked {
+                outcome,
+                wait: Some(wait),
+            } => write!(
+                f,
+                "{}, wait {}s before trying again",
+                outcome,
+                wait.as_secs()
+            ),
+            Response::Checked { outcome, .. } => write!(f, "{}", outcome),
+            Response::TooSoon(wait) => {
+                write!(f, "answered too recently, {}s left to wait", wait.as_secs())
+            }
+            Response::WrongLevel => write!(f, "not the right part, is it already solved?"),
+        }
+    }
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::AlreadySolved(answer) => write!(f, "already solved with {}", answer),
+            SubmitError::AlreadyTried(outcome) => {
+                write!(f, "this answer was already submitted, it was {}", outcome)
+            }
+            SubmitError::Cooldown(wait) => {
+                write!(f, "wait {}s before submitting again", wait.as_secs())
+            }
+            SubmitError::UnknownResponse(page) => {
+                write!(f, "unrecognized response from server: {}", page.trim())
+            }
+            SubmitError::Fetch(e) => e.fmt(f),
+            SubmitError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            SubmitError::Toml(path, e) => write!(f, "invalid log {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Outcome, Response, SubmitError, SubmitLog};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn page(text: &str) -> String {
+        format!("<main>\n<article><p>{}</p></article>\n</main>", text)
+    }
+
+    #[test]
+    pub fn test_parse_response() {
+        let right = page("That's the right answer!  You are <em>one gold star</em> closer.");
+        let high = page(
+            "That's not the right answer; your answer is too high.  \
+             Please wait one minute before trying again.",
+        );
+        let wrong =
+            page("That's not the right answer.  Please wait 5 minutes before trying again.");
+        let soon = page("You gave an answer too recently.  You have 1m 12s left to wait.");
+        let level =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+
+        let checked = |outcome, wait: Option<u64>| Response::Checked {
+            outcome,
+            wait: wait.map(Duration::from_secs),
+        };
+        assert_eq!(
+            Response::parse(&right),
+            Some(checked(Outcome::Correct, None))
+        );
+        assert_eq!(
+            Response::parse(&high),
+            Some(checked(Outcome::TooHigh, Some(60)))
+        );
+        assert_eq!(
+            Response::parse(&wrong),
+            Some(checked(Outcome::Incorrect, Some(300)))
+        );
+        assert_eq!(
+            Response::parse(&soon),
+            Some(Response::TooSoon(Duration::from_secs(72)))
+        );
+        assert_eq!(Response::parse(&level), Some(Response::WrongLevel));
+        assert_eq!(Response::parse("<html></html>"), None);
+    }
+
+    #[test]
+    pub fn test_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.toml");
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+
+        let mut log = SubmitLog::load(&path).unwrap();
+        assert!(log.check(2021, 1, 1, "42", at(1000)).is_ok());
+
+        let too_low = Response::Checked {
+            outcome: Outcome::TooLow,
+            wait: Some(Duration::from_secs(60)),
+        };
+        log.record(2021, 1, 1, "42", too_low, at(1000));
+        log.save().unwrap();
+
+        let log = SubmitLog::load(&path).unwrap();
+        assert!(matches!(
+            log.check(2021, 1, 1, "42", at(2000)),
+            Err(SubmitError::AlreadyTried(Outcome::TooLow))
+        ));
+        assert!(matches!(
+            log.check(2021, 1, 1, "43", at(1030)),
+            Err(SubmitError::Cooldown(wait)) if wait == Duration::from_secs(30)
+        ));
+        assert!(log.check(2021, 1, 1, "43", at(1060)).is_ok());
+        assert!(log.check(2021, 1, 2, "42", SystemTime::now()).is_ok());
+    }
+}
@@ -0,0 +1,163 @@
+//! Helpers for checking the days against slow reference solutions, and with mangled inputs
+
+use crate::answer::Answer;
+use crate::error::SolveError;
+use crate::known_answers::Parts;
+use crate::output::{panic_message, panic_status, Status};
+use crate::solution::{Generator, Solution, Streaming};
+use fastrand::Rng;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Number of seeds to generate inputs from, can be raised with `AOC_PROPERTY_SEEDS`
+const DEFAULT_SEEDS: u64 = 10;
+
+fn seeds() -> u64 {
+    std::env::var("AOC_PROPERTY_SEEDS")
+        .ok()
+        .and_then(|seeds| seeds.parse().ok())
+        .unwrap_or(DEFAULT_SEEDS)
+}
+
+/// Check that both parts of `S` agree with the reference solutions on generated inputs
+///
+/// Every seed is tried with inputs of increasing size up to `max_size`, so that the first
+/// failure is on an input that is as small as possible.
+pub fn check_against_reference<S: Solution + Generator>(
+    reference1: impl Fn(&S::Input) -> Answer,
+    reference2: impl Fn(&S::Input) -> Answer,
+    max_size: usize,
+) {
+    let mut sizes = vec![];
+    let mut size = 1;
+    while size < max_size {
+        sizes.push(size);
+        size = size * 3 / 2 + 1;
+    }
+    sizes.push(max_size);
+
+    for seed in 0..seeds() {
+        for &size in &sizes {
+            let input = S::generate(&mut Rng::with_seed(seed), size);
+            let parsed = S::parse(&input)
+                .unwrap_or_else(|e| panic!("seed {}, size {}: {}\n{}", seed, size, e, input));
+
+            let check = |part, answer: Result<Answer, SolveError>, expected: Answer| {
+                assert_eq!(
+                    answer,
+                    Ok(expected),
+                    "part {} disagrees with the reference on seed {}, size {}:\n{}",
+                    part,
+                    seed,
+                    size,
+                    input
+                )
+            };
+            check(1, S::part1(&parsed), reference1(&parsed));
+            check(2, S::part2(&parsed), reference2(&parsed));
+        }
+    }
+}
+
+/// Run `f`, unless it runs into a `todo!()`, like a day that is fresh from the template does
+///
+/// For the tests that go over every day, so that they pass while a new day is being written. Any
+/// other panic is passed on.
+pub fn skip_todo(what: &str, f: impl FnOnce()) {
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(f)) {
+        let message = panic_message(&*payload);
+        if panic_status(&message) != Status::NotImplemented {
+            panic::resume_unwind(payload);
+        }
+        eprintln!("skipping {}: {}", what, message);
+    }
+}
+
+/// Solve one part of a day's example and compare it to the answer in the example's `.toml`
+///
+/// Without an answer, this fails with the one it got, to make it easy to fill in.
+pub fn check_example(year: u32, day: u32, part: u32, input: &str, answers: Option<&str>) {
+    let entry = crate::day(year, day).expect("day is not in the registry");
+    let parsed = (entry.parse)(input).unwrap_or_else(|e| panic!("{}", e));
+    let answer = match part {
+        1 => (entry.part1)(&parsed),
+        _ => (entry.part2)(&parsed),
+    };
+    let answer = answer.unwrap_or_else(|e| panic!("{}", e));
+
+    let expected = answers.and_then(|answers| {
+        let parts: Parts = toml::from_str(answers).expect("invalid example answers");
+        parts.get(part).cloned()
+    });
+    match expected {
+        Some(expected) => assert_eq!(answer, expected),
+        None => panic!("no answer to part {} to compare with, got {}", part, answer),
+    }
+}
+
+/// Check that streaming `S` gives the same answers as parsing the whole input
+///
+/// Tried on generated inputs, and on mangled ones that still parse. Streaming may accept inputs
+/// that don't parse, as long as the parts it solves don't need what's wrong with them, and it
+/// fails with the same [SolveError] as the part it solves that has no answer.
+pub fn check_streaming<S: Streaming + Generator>() {
+    for seed in 0..seeds() {
+        let mut rng = Rng::with_seed(seed);
+        let input = S::generate(&mut rng, 100);
+        let mangled = String::from_utf8_lossy(&mangle(&mut rng, &input)).into_owned();
+
+        for input in [input, mangled] {
+            let parsed = match S::parse(&input) {
+                Ok(parsed) => parsed,
+                Err(_) => continue,
+            };
+            let solved = [S::part1(&parsed), S::part2(&parsed)];
+            let streamed = match S::stream(&mut input.as_bytes()) {
+                Ok(streamed) => streamed,
+                Err(e) => panic!("seed {}: {}\n{}", seed, e, input),
+            };
+
+            let streamed = [streamed.part1, streamed.part2];
+            for (part, (answer, expected)) in streamed.into_iter().zip(solved).enumerate() {
+                if let Some(answer) = answer {
+                    let part = part + 1;
+                    assert_eq!(
+                        answer, expected,
+                        "part {} on seed {}:\n{}",
+                        part, seed, input
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Bytes that are likely to get a parser into trouble
+const INTERESTING: &[u8] = b"0123456789,-> |\n\nabcdefgx";
+
+/// Randomly damage an input a few times, to see if anything panics on the result
+///
+/// Most of the input survives, so the result usually gets past the first few checks of a parser.
+pub fn mangle(rng: &mut Rng, input: &str) -> Vec<u8> {
+    let mut bytes = input.as_bytes().to_vec();
+
+    for _ in 0..rng.usize(1..=4) {
+        let at = rng.usize(..=bytes.len());
+        match rng.u8(0..5) {
+            0 => {
+                let len = rng.usize(..=(bytes.len() - at).min(8));
+                bytes.drain(at..at + len);
+            }
+            1 => bytes.insert(at, INTERESTING[rng.usize(..INTERESTING.len())]),
+            2 => {
+                bytes.splice(at..at, b"99999999999".iter().copied());
+            }
+            3 => bytes.truncate(at),
+            _ => {
+                let copy = bytes[at..].to_vec();
+                bytes.extend(copy);
+            }
+        }
+    }
+
+    bytes
+}
@@ -0,0 +1,108 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, SolveError, StreamError};
+use crate::parsing::Source;
+use crate::solution::{for_each_line, Generator, Solution, Streamed, Streaming};
+use fastrand::Rng;
+use std::io::BufRead;
+
+const DAY: u32 = 1;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day01;
+solution!(Day01);
+
+fn solver<const W: usize>(depths: &[i32]) -> usize {
+    depths.windows(W).filter(|win| win[0] < win[W - 1]).count()
+}
+
+impl Solution for Day01 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        let source = Source::new(DAY, input);
+        source.lines().map(|line| source.number(line)).collect()
+    }
+
+    fn part1(depths: &Vec<i32>) -> Result<Answer, SolveError> {
+        Ok(solver::<2>(depths).into())
+    }
+
+    fn part2(depths: &Vec<i32>) -> Result<Answer, SolveError> {
+        Ok(solver::<4>(depths).into())
+    }
+}
+
+/// Only the last three depths are kept, which is as far back as part 2 looks
+impl Streaming for Day01 {
+    fn stream(input: &mut dyn BufRead) -> Result<Streamed, StreamError> {
+        let mut last = [0; 3];
+        let (mut seen, mut increases, mut window_increases) = (0, 0usize, 0usize);
+        for_each_line(input, |line| {
+            let depth: i32 = Source::new(DAY, line).number(line)?;
+            if seen >= 1 && last[(seen - 1) % 3] < depth {
+                increases += 1;
+            }
+            if seen >= 3 && last[seen % 3] < depth {
+                window_increases += 1;
+            }
+            last[seen % 3] = depth;
+            seen += 1;
+            Ok(())
+        })?;
+
+        Ok(Streamed {
+            part1: Some(Ok(increases.into())),
+            part2: Some(Ok(window_increases.into())),
+        })
+    }
+}
+
+/// `size` is the number of depth readings
+impl Generator for Day01 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut depth = rng.i32(100..200);
+        (0..size)
+            .map(|_| {
+                let line = format!("{}\n", depth);
+                depth = (depth + rng.i32(-10..=30)).max(0);
+                line
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, reference, Day01};
+    use crate::error::StreamError;
+    use crate::solution::Streaming;
+    use crate::testing::{check_against_reference, check_streaming};
+
+    #[test]
+    pub fn test_parse_error() {
+        let error = parse("199\n2OO\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.token, "2OO");
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day01>(
+            |input| reference::part1(input),
+            |input| reference::part2(input),
+            300,
+        );
+    }
+
+    #[test]
+    pub fn test_stream() {
+        check_streaming::<Day01>();
+
+        match Day01::stream(&mut "199\n200\n2OO\n".as_bytes()) {
+            Err(StreamError::Parse(error)) => assert_eq!((error.line, error.column), (3, 1)),
+            result => panic!("expected a parse error, got {:?}", result),
+        }
+    }
+}
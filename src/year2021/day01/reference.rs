@@ -0,0 +1,18 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use crate::answer::Answer;
+
+pub fn part1(depths: &[i32]) -> Answer {
+    let mut increases = 0;
+    for i in 1..depths.len() {
+        if depths[i] > depths[i - 1] {
+            increases += 1;
+        }
+    }
+    increases.into()
+}
+
+pub fn part2(depths: &[i32]) -> Answer {
+    let sums: Vec<i32> = depths.windows(3).map(|w| w[0] + w[1] + w[2]).collect();
+    part1(&sums)
+}
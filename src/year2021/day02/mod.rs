@@ -0,0 +1,194 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, ParseErrorKind, SolveError, StreamError};
+use crate::parsing::Source;
+use crate::solution::{for_each_line, Generator, Solution, Streamed, Streaming};
+use fastrand::Rng;
+use num_bigint::BigInt;
+use std::io::BufRead;
+
+const DAY: u32 = 2;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day02;
+solution!(Day02);
+
+/// Parse one line of the `source` into a move
+fn parse_move(source: Source, line: &str) -> Result<(i32, i32), ParseError> {
+    let (dir, dist) = source.split_once(line, " ")?;
+    let dist: i32 = source.number_in(dist, 0..)?;
+
+    match dir {
+        "forward" => Ok((dist, 0)),
+        "down" => Ok((0, dist)),
+        "up" => Ok((0, -dist)),
+        _ => Err(source.error(dir, ParseErrorKind::UnexpectedToken)),
+    }
+}
+
+/// Where the submarine is, following along with every move
+#[derive(Default, Clone, Copy)]
+struct Course {
+    pos: i128,
+    depth: i128,
+    aim: i128,
+}
+
+impl Course {
+    /// Make a move the way part 1 reads it, or `None` if anything would overflow
+    fn step(self, (x, y): (i32, i32)) -> Option<Course> {
+        Some(Course {
+            pos: self.pos.checked_add(x as i128)?,
+            depth: self.depth.checked_add(y as i128)?,
+            aim: 0,
+        })
+    }
+
+    /// Make a move the way part 2 reads it, where up and down only change the aim
+    fn aimed_step(self, (x, y): (i32, i32)) -> Option<Course> {
+        let (x, aim) = (x as i128, self.aim.checked_add(y as i128)?);
+        Some(Course {
+            pos: self.pos.checked_add(x)?,
+            depth: self.depth.checked_add(x.checked_mul(aim)?)?,
+            aim,
+        })
+    }
+
+    /// The product of the position and the depth, which can be too big for any primitive
+    fn product(self) -> Answer {
+        (BigInt::from(self.pos) * self.depth).into()
+    }
+}
+
+/// Where following the moves with `step` ends up, or `None` if it overflows on the way
+fn follow(moves: &[(i32, i32)], step: fn(Course, (i32, i32)) -> Option<Course>) -> Option<Course> {
+    moves
+        .iter()
+        .try_fold(Course::default(), |course, &moved| step(course, moved))
+}
+
+/// The answer to `part` from where the submarine ended up, if it didn't overflow on the way
+fn answer(part: u32, course: Option<Course>) -> Result<Answer, SolveError> {
+    // it takes billions of moves for an i128 to overflow
+    course
+        .map(Course::product)
+        .ok_or_else(|| SolveError::new(DAY, part, "the position or depth overflows"))
+}
+
+impl Solution for Day02 {
+    type Input = Vec<(i32, i32)>;
+
+    fn parse(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
+        let source = Source::new(DAY, input);
+        source
+            .lines()
+            .map(|line| parse_move(source, line))
+            .collect()
+    }
+
+    fn part1(moves: &Vec<(i32, i32)>) -> Result<Answer, SolveError> {
+        answer(1, follow(moves, Course::step))
+    }
+
+    fn part2(moves: &Vec<(i32, i32)>) -> Result<Answer, SolveError> {
+        answer(2, follow(moves, Course::aimed_step))
+    }
+}
+
+impl Streaming for Day02 {
+    fn stream(input: &mut dyn BufRead) -> Result<Streamed, StreamError> {
+        let (mut course, mut aimed) = (Some(Course::default()), Some(Course::default()));
+        for_each_line(input, |line| {
+            let moved = parse_move(Source::new(DAY, line), line)?;
+            course = course.and_then(|course| course.step(moved));
+            aimed = aimed.and_then(|aimed| aimed.aimed_step(moved));
+            Ok(())
+        })?;
+
+        Ok(Streamed {
+            part1: Some(answer(1, course)),
+            part2: Some(answer(2, aimed)),
+        })
+    }
+}
+
+/// `size` is the number of moves. The submarine never goes above the surface.
+impl Generator for Day02 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut depth = 0;
+        (0..size)
+            .map(|_| {
+                let dist = rng.i32(1..10);
+                match rng.u8(0..3) {
+                    0 => format!("forward {}\n", dist),
+                    1 => {
+                        depth += dist;
+                        format!("down {}\n", dist)
+                    }
+                    _ if depth >= dist => {
+                        depth -= dist;
+                        format!("up {}\n", dist)
+                    }
+                    _ => format!("forward {}\n", dist),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{answer, parse, part1, part2, reference, Course, Day02};
+    use crate::answer::Answer;
+    use crate::error::{ParseErrorKind, StreamError};
+    use crate::solution::Streaming;
+    use crate::testing::{check_against_reference, check_streaming};
+
+    #[test]
+    pub fn test_parse_error() {
+        let error = parse("forward 5\nsideways 3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.token, "sideways");
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedToken);
+    }
+
+    #[test]
+    pub fn test_big_answers() {
+        // the answers overflowed an i32 before, and part 2 doesn't even fit an i64
+        let input = "down 2147483647\nforward 2147483647\n";
+        let max = i32::MAX as i64;
+        assert_eq!(part1(input).unwrap(), Answer::from(max * max));
+        assert_eq!(
+            part2(input).unwrap(),
+            Answer::parse(&(max as i128).pow(3).to_string())
+        );
+    }
+
+    #[test]
+    pub fn test_overflow() {
+        let course = Course {
+            depth: i128::MAX,
+            ..Course::default()
+        };
+        assert!(course.step((0, 1)).is_none());
+        // in part 2, down only changes the aim
+        assert!(course.aimed_step((0, 1)).is_some());
+        assert_eq!(answer(2, None).unwrap_err().part, 2);
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day02>(reference::part1, reference::part2, 300);
+    }
+
+    #[test]
+    pub fn test_stream() {
+        check_streaming::<Day02>();
+
+        match Day02::stream(&mut "forward 5\ndown x\n".as_bytes()) {
+            Err(StreamError::Parse(error)) => assert_eq!((error.line, error.column), (2, 6)),
+            result => panic!("expected a parse error, got {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use crate::answer::Answer;
+
+pub fn part1(moves: &Vec<(i32, i32)>) -> Answer {
+    let (mut pos, mut depth) = (0, 0);
+    for &(forward, down) in moves {
+        pos += forward;
+        depth += down;
+    }
+    (pos * depth).into()
+}
+
+pub fn part2(moves: &Vec<(i32, i32)>) -> Answer {
+    let (mut pos, mut depth, mut aim) = (0, 0, 0);
+    for &(forward, down) in moves {
+        if forward != 0 {
+            pos += forward;
+            depth += aim * forward;
+        } else {
+            aim += down;
+        }
+    }
+    (pos * depth).into()
+}
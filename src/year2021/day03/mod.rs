@@ -0,0 +1,248 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, ParseErrorKind, SolveError, StreamError};
+use crate::parsing::Source;
+use crate::solution::{for_each_line, Generator, Solution, Streamed, Streaming};
+use fastrand::Rng;
+use std::io::BufRead;
+
+const DAY: u32 = 3;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day03;
+solution!(Day03);
+
+fn check_bit(number: u64, bit: usize) -> bool {
+    let mask = 1 << bit;
+    (number & mask) != 0
+}
+
+/// Parse a line of the `source` that is `bits` bits wide
+fn parse_line(source: Source, line: &str, bits: usize) -> Result<u64, ParseError> {
+    if let Some((i, c)) = line.char_indices().find(|&(_, c)| c != '0' && c != '1') {
+        let token = &line[i..i + c.len_utf8()];
+        return Err(source.error(token, ParseErrorKind::UnexpectedToken));
+    }
+    if line.len() != bits {
+        return Err(source.error(
+            line,
+            ParseErrorKind::WrongLength {
+                expected: bits,
+                found: line.len(),
+            },
+        ));
+    }
+    u64::from_str_radix(line, 2).map_err(|_| source.error(line, ParseErrorKind::InvalidNumber))
+}
+
+/// Add a value to the tally of how many more values have each bit set than not
+fn tally(bit_count: &mut [i64], num: u64) {
+    for (i, count) in bit_count.iter_mut().enumerate() {
+        let bit_set = check_bit(num, i);
+        // branchless baby
+        *count += (bit_set as i64) * 2 - 1;
+    }
+}
+
+/// The answer to part 1, from the tally of every value
+fn power_consumption(bit_count: &[i64]) -> u128 {
+    let mut gamma = 0u64;
+    for &bit in bit_count.iter().rev() {
+        gamma <<= 1;
+        if bit >= 0 {
+            gamma |= 1;
+        }
+    }
+
+    let mut mask = 0;
+    for _ in 0..bit_count.len() {
+        mask <<= 1;
+        mask |= 1;
+    }
+
+    let epsilon = gamma ^ mask;
+
+    gamma as u128 * epsilon as u128
+}
+
+impl Solution for Day03 {
+    type Input = (Vec<u64>, usize);
+
+    /// Values can be up to 64 bits wide
+    fn parse(input: &str) -> Result<(Vec<u64>, usize), ParseError> {
+        let source = Source::new(DAY, input);
+
+        let first = source
+            .lines()
+            .next()
+            .ok_or_else(|| source.error(input, ParseErrorKind::UnexpectedEnd))?;
+        let bits = first.len();
+        if bits > 64 {
+            return Err(source.error(first, ParseErrorKind::OutOfRange));
+        }
+
+        let data = source
+            .lines()
+            .map(|line| parse_line(source, line, bits))
+            .collect::<Result<Vec<u64>, _>>()?;
+        Ok((data, bits))
+    }
+
+    fn part1((data, bits): &(Vec<u64>, usize)) -> Result<Answer, SolveError> {
+        let mut bit_count = vec![0; *bits];
+        for &num in data {
+            tally(&mut bit_count, num);
+        }
+        Ok(power_consumption(&bit_count).into())
+    }
+
+    fn part2((data, bits): &(Vec<u64>, usize)) -> Result<Answer, SolveError> {
+        let (oxygen, scrubber) = ratings(data, *bits)
+            .ok_or_else(|| SolveError::new(DAY, 2, "the values don't filter down to one"))?;
+        Ok((oxygen as u128 * scrubber as u128).into())
+    }
+}
+
+/// Filter the report down to a single value, bit by bit, keeping the values whose bit is
+/// chosen by `filter`. None if no value or several identical values remain.
+fn decode(mut data: Vec<u64>, filter: impl Fn(usize, usize) -> bool, bits: usize) -> Option<u64> {
+    for bit in (0..bits).rev() {
+        if data.len() <= 1 {
+            break;
+        }
+
+        let ones: usize = data.iter().map(|&line| check_bit(line, bit) as usize).sum();
+        let zeros = data.len() - ones;
+
+        let mask = 1 << bit;
+
+        let cmp = if filter(ones, zeros) { 1 << bit } else { 0 };
+
+        data.retain(|line| line & mask == cmp);
+    }
+
+    match data[..] {
+        [value] => Some(value),
+        _ => None,
+    }
+}
+
+/// The oxygen generator and CO2 scrubber ratings, if the report has them
+fn ratings(data: &[u64], bits: usize) -> Option<(u64, u64)> {
+    let oxygen = decode(data.to_vec(), |ones, zeros| ones >= zeros, bits)?;
+    let scrubber = decode(data.to_vec(), |ones, zeros| ones < zeros, bits)?;
+    Some((oxygen, scrubber))
+}
+
+/// Only part 1, as part 2 filters the values over and over
+impl Streaming for Day03 {
+    fn stream(input: &mut dyn BufRead) -> Result<Streamed, StreamError> {
+        let mut bit_count: Vec<i64> = vec![];
+        let lines = for_each_line(input, |line| {
+            let source = Source::new(DAY, line);
+            if bit_count.is_empty() {
+                if line.len() > 64 {
+                    return Err(source.error(line, ParseErrorKind::OutOfRange));
+                }
+                bit_count = vec![0; line.len()];
+            }
+            let num = parse_line(source, line, bit_count.len())?;
+            tally(&mut bit_count, num);
+            Ok(())
+        })?;
+
+        if lines == 0 {
+            return Err(ParseError::new(DAY, "", "", ParseErrorKind::UnexpectedEnd).into());
+        }
+        Ok(Streamed {
+            part1: Some(Ok(power_consumption(&bit_count).into())),
+            part2: None,
+        })
+    }
+}
+
+/// `size` is the number of distinct report lines, at least 2. They are 12 bits wide unless more
+/// than 4096 lines are requested.
+impl Generator for Day03 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let bits = if size <= 1 << 12 { 12 } else { 16 };
+        let size = size.clamp(2, 1 << bits);
+        let mut numbers: Vec<u64> = (0..1 << bits).collect();
+
+        loop {
+            rng.shuffle(&mut numbers);
+            if ratings(&numbers[..size], bits).is_some() {
+                break;
+            }
+        }
+
+        numbers[..size]
+            .iter()
+            .map(|n| format!("{:0width$b}\n", n, width = bits))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, part1, part2, reference, Day03};
+    use crate::error::{SolveError, StreamError};
+    use crate::solution::{Solution, Streaming};
+    use crate::testing::{check_against_reference, check_streaming};
+
+    #[test]
+    pub fn test_parse_error() {
+        let error = parse("00100\n11110\n10210\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.token, "2");
+
+        // a sign would be accepted by a plain radix parse
+        let error = parse("0101\n+101\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.token, "+");
+    }
+
+    #[test]
+    pub fn test_no_solution() {
+        // the two values are the same, so part 2 can't pick one
+        let parsed = parse("101\n101\n").unwrap();
+        assert_eq!(Day03::part1(&parsed), Ok(10.into()));
+        let error = Day03::part2(&parsed).unwrap_err();
+        assert_eq!((error.day, error.part), (3, 2));
+        assert!(part2("101\n101\n").unwrap_err().is::<SolveError>());
+    }
+
+    #[test]
+    pub fn test_wide_values() {
+        // too wide for the answer to fit the u32 it used to be multiplied in
+        let input = format!("{0}1\n{0}0\n{1}\n", "1".repeat(39), "0".repeat(40));
+        assert_eq!(part1(&input).unwrap(), ((1u64 << 40) - 2).into());
+
+        let parsed = parse(&input).unwrap();
+        assert_eq!(Day03::part2(&parsed), Ok(reference::part2(&parsed)));
+        assert!(parse(&"1".repeat(65)).is_err());
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day03>(reference::part1, reference::part2, 100);
+    }
+
+    #[test]
+    pub fn test_stream() {
+        check_streaming::<Day03>();
+
+        // part 2 has no answer here, which doesn't stop part 1
+        let streamed = Day03::stream(&mut "101\n101\n".as_bytes()).unwrap();
+        assert_eq!(
+            (streamed.part1, streamed.part2),
+            (Some(Ok(10.into())), None)
+        );
+
+        match Day03::stream(&mut "00100\n11110\n1011\n".as_bytes()) {
+            Err(StreamError::Parse(error)) => assert_eq!((error.line, error.column), (3, 1)),
+            result => panic!("expected a parse error, got {:?}", result),
+        }
+    }
+}
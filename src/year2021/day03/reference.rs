@@ -0,0 +1,57 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use crate::answer::Answer;
+
+/// The report as strings of '0' and '1', like in the puzzle
+fn lines((data, bits): &(Vec<u64>, usize)) -> Vec<String> {
+    data.iter()
+        .map(|n| format!("{:0width$b}", n, width = bits))
+        .collect()
+}
+
+fn count_ones(lines: &[String], i: usize) -> usize {
+    lines
+        .iter()
+        .filter(|line| line.as_bytes()[i] == b'1')
+        .count()
+}
+
+fn to_number(bits: &str) -> u128 {
+    u128::from_str_radix(bits, 2).unwrap()
+}
+
+pub fn part1(input: &(Vec<u64>, usize)) -> Answer {
+    let lines = lines(input);
+    let (mut gamma, mut epsilon) = (String::new(), String::new());
+    for i in 0..input.1 {
+        let ones = count_ones(&lines, i);
+        let zeros = lines.len() - ones;
+        gamma.push(if ones >= zeros { '1' } else { '0' });
+        epsilon.push(if ones >= zeros { '0' } else { '1' });
+    }
+    (to_number(&gamma) * to_number(&epsilon)).into()
+}
+
+fn rating(mut lines: Vec<String>, keep: fn(usize, usize) -> char) -> u128 {
+    let mut i = 0;
+    while lines.len() > 1 {
+        let ones = count_ones(&lines, i);
+        let zeros = lines.len() - ones;
+        let wanted = keep(ones, zeros);
+        lines.retain(|line| line.as_bytes()[i] as char == wanted);
+        i += 1;
+    }
+    to_number(&lines[0])
+}
+
+pub fn part2(input: &(Vec<u64>, usize)) -> Answer {
+    let oxygen = rating(
+        lines(input),
+        |ones, zeros| if ones >= zeros { '1' } else { '0' },
+    );
+    let scrubber = rating(
+        lines(input),
+        |ones, zeros| if ones >= zeros { '0' } else { '1' },
+    );
+    (oxygen * scrubber).into()
+}
@@ -0,0 +1,287 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, ParseErrorKind, SolveError};
+use crate::parsing::Source;
+use crate::solution::{Generator, Solution};
+use fastrand::Rng;
+use hashers::fx_hash::FxHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasherDefault;
+
+type Number = u32;
+type Pos = (usize, usize);
+
+type Hasher = BuildHasherDefault<FxHasher>;
+
+const DAY: u32 = 4;
+const BOARD_CELLS: usize = 25;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day04;
+solution!(Day04);
+
+#[derive(Debug)]
+pub struct Bingo {
+    pub numbers: Vec<Number>,
+    pub boards: Vec<Board>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Board {
+    pub numbers: HashMap<Number, Pos, Hasher>,
+}
+
+fn row((x, y): Pos) -> impl Iterator<Item = Pos> {
+    (0..5).filter(move |&rx| rx != x).map(move |rx| (rx, y))
+}
+
+fn col((x, y): Pos) -> impl Iterator<Item = Pos> {
+    (0..5).filter(move |&cy| cy != y).map(move |cy| (x, cy))
+}
+
+fn check_if_board_won(placed: &HashSet<Pos, Hasher>, check: &[Pos]) -> bool {
+    check
+        .iter()
+        .any(|&p| row(p).all(|p| placed.contains(&p)) || col(p).all(|p| placed.contains(&p)))
+}
+
+/// Play until a board wins while `return_condition` holds for the number of boards still in play
+///
+/// None if the numbers run out first.
+fn solver(
+    bingo: &Bingo,
+    mut return_condition: impl FnMut(usize) -> bool,
+) -> Option<(Board, HashSet<Pos, Hasher>, Number)> {
+    let mut placed: Vec<HashSet<Pos, Hasher>> = vec![HashSet::default(); bingo.boards.len()];
+    let mut boards = bingo.boards.clone();
+
+    for &num in &bingo.numbers {
+        let mut won: HashSet<_, Hasher> = HashSet::default();
+
+        for i in 0..boards.len() {
+            if let Some(&pos) = boards[i].numbers.get(&num) {
+                placed[i].insert(pos);
+
+                if check_if_board_won(&placed[i], &[pos]) {
+                    if return_condition(placed.len()) {
+                        return Some((boards.remove(i), placed.remove(i), num));
+                    }
+
+                    won.insert(i);
+                }
+            }
+        }
+
+        if !won.is_empty() {
+            fn drain_i<T, I: Iterator<Item = T>, F: Fn(usize) -> bool>(i: I, f: F) -> Vec<T> {
+                i.enumerate()
+                    .filter(|&(i, _)| f(i))
+                    .map(|(_, t)| t)
+                    .collect()
+            }
+            placed = drain_i(placed.into_iter(), |i| !won.contains(&i));
+            boards = drain_i(boards.into_iter(), |i| !won.contains(&i));
+
+            won.clear();
+        }
+    }
+
+    None
+}
+
+/// The score of a board that just won, which takes more than 64 bits for the largest numbers
+fn score_board(board: &Board, placed: &HashSet<Pos, Hasher>, final_num: Number) -> u128 {
+    let unmarked_sum: u128 = board
+        .numbers
+        .iter()
+        .filter(|(_, pos)| !placed.contains(pos))
+        .map(|(&num, _)| num as u128)
+        .sum();
+
+    unmarked_sum * final_num as u128
+}
+
+impl Solution for Day04 {
+    type Input = Bingo;
+
+    fn parse(input: &str) -> Result<Bingo, ParseError> {
+        let source = Source::new(DAY, input);
+        let mut blocks = source.blocks();
+
+        let numbers = blocks
+            .next()
+            .ok_or_else(|| source.error(input, ParseErrorKind::UnexpectedEnd))?;
+        let numbers = source
+            .separated(numbers, ',', |n| source.number(n))
+            .collect::<Result<Vec<Number>, _>>()?;
+
+        let boards = blocks
+            .map(|block| {
+                let cells: Vec<&str> = block.split_whitespace().collect();
+                if cells.len() != BOARD_CELLS {
+                    return Err(source.error(
+                        block,
+                        ParseErrorKind::WrongLength {
+                            expected: BOARD_CELLS,
+                            found: cells.len(),
+                        },
+                    ));
+                }
+
+                let mut numbers = HashMap::default();
+                for (i, n) in cells.into_iter().enumerate() {
+                    if numbers.insert(source.number(n)?, (i % 5, i / 5)).is_some() {
+                        // a number can only be on a board once
+                        return Err(source.error(n, ParseErrorKind::UnexpectedToken));
+                    }
+                }
+
+                Ok(Board { numbers })
+            })
+            .collect::<Result<Vec<Board>, _>>()?;
+
+        Ok(Bingo { numbers, boards })
+    }
+
+    fn part1(bingo: &Bingo) -> Result<Answer, SolveError> {
+        let (board, placed, final_num) =
+            solver(bingo, |_| true).ok_or_else(|| SolveError::new(DAY, 1, "no board ever wins"))?;
+        Ok(score_board(&board, &placed, final_num).into())
+    }
+
+    /// Boards that tie for last leave no single last winner, and so no answer
+    fn part2(bingo: &Bingo) -> Result<Answer, SolveError> {
+        let (board, placed, final_num) = solver(bingo, |l| l == 1)
+            .ok_or_else(|| SolveError::new(DAY, 2, "no single board is the last to win"))?;
+        Ok(score_board(&board, &placed, final_num).into())
+    }
+}
+
+/// The index of the drawn number that makes a board win, or `usize::MAX` if it never wins
+///
+/// `turn_of` gives the index of the first draw of a number, if it is drawn at all.
+fn win_turn(board: &[Number], turn_of: impl Fn(Number) -> Option<usize>) -> usize {
+    let turn = |x: usize, y: usize| turn_of(board[y * 5 + x]).unwrap_or(usize::MAX);
+    let rows = (0..5).map(|y| (0..5).map(|x| turn(x, y)).max().unwrap());
+    let cols = (0..5).map(|x| (0..5).map(|y| turn(x, y)).max().unwrap());
+    rows.chain(cols).min().unwrap()
+}
+
+/// `size` is the number of boards. Every number is drawn, and no two boards tie for first or
+/// last to win, so that both parts have a single answer.
+impl Generator for Day04 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const NUMBERS: Number = 100;
+
+        let mut numbers: Vec<Number> = (0..NUMBERS).collect();
+        let mut random_board = |rng: &mut Rng| {
+            rng.shuffle(&mut numbers);
+            numbers[..BOARD_CELLS].to_vec()
+        };
+
+        let mut draws: Vec<Number> = (0..NUMBERS).collect();
+        rng.shuffle(&mut draws);
+        let mut turn_of = vec![0; NUMBERS as usize];
+        for (turn, &n) in draws.iter().enumerate() {
+            turn_of[n as usize] = turn;
+        }
+
+        let mut boards: Vec<Vec<Number>> = (0..size.max(1)).map(|_| random_board(rng)).collect();
+        loop {
+            let turns: Vec<usize> = boards
+                .iter()
+                .map(|b| win_turn(b, |n| Some(turn_of[n as usize])))
+                .collect();
+            let first = *turns.iter().min().unwrap();
+            let last = *turns.iter().max().unwrap();
+
+            let tied = (0..boards.len()).skip(1).find(|&i| {
+                let earlier = &turns[..i];
+                (turns[i] == first && earlier.contains(&first))
+                    || (turns[i] == last && earlier.contains(&last))
+            });
+
+            match tied {
+                Some(i) => boards[i] = random_board(rng),
+                None => break,
+            }
+        }
+
+        let draws: Vec<String> = draws.iter().map(Number::to_string).collect();
+        let mut input = draws.join(",");
+        input.push('\n');
+        for board in boards {
+            input.push('\n');
+            for row in board.chunks(5) {
+                let row: Vec<String> = row.iter().map(|n| format!("{:>2}", n)).collect();
+                input.push_str(&row.join(" "));
+                input.push('\n');
+            }
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, reference, Day04};
+    use crate::error::ParseErrorKind;
+    use crate::solution::Solution;
+    use crate::testing::check_against_reference;
+
+    #[test]
+    pub fn test_parse_error() {
+        let input = "7,4,9\n\n1 2 3\n4 5 6\n";
+        let error = parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::WrongLength {
+                expected: 25,
+                found: 6
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_no_solution() {
+        let (draws, boards) = include_str!("examples/example.txt")
+            .split_once("\n\n")
+            .unwrap();
+
+        // not enough numbers are drawn for any board to win
+        let bingo = parse(&format!("7,4,9\n\n{}", boards)).unwrap();
+        assert_eq!(Day04::part1(&bingo).unwrap_err().part, 1);
+        assert_eq!(Day04::part2(&bingo).unwrap_err().part, 2);
+
+        // the board that wins last, twice, so neither copy is the last to win
+        let board = boards.split("\n\n").nth(1).unwrap();
+        let bingo = parse(&format!("{}\n\n{}\n\n{}", draws, board, board)).unwrap();
+        assert!(Day04::part1(&bingo).is_ok());
+        assert!(Day04::part2(&bingo).is_err());
+    }
+
+    #[test]
+    pub fn test_big_numbers() {
+        // the score takes more than 64 bits when the numbers are close to u32::MAX
+        let cells: Vec<u32> = (0..25).map(|i| u32::MAX - i).collect();
+        let rows: Vec<String> = cells
+            .chunks(5)
+            .map(|row| row.iter().map(u32::to_string).collect::<Vec<_>>().join(" "))
+            .collect();
+        let draws: Vec<String> = cells[..5].iter().map(u32::to_string).collect();
+        let bingo = parse(&format!("{}\n\n{}\n", draws.join(","), rows.join("\n"))).unwrap();
+
+        let unmarked: u128 = cells[5..].iter().map(|&n| n as u128).sum();
+        assert_eq!(
+            Day04::part1(&bingo),
+            Ok((unmarked * cells[4] as u128).into())
+        );
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day04>(reference::part1, reference::part2, 30);
+    }
+}
@@ -0,0 +1,62 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use super::{Bingo, Number};
+use crate::answer::Answer;
+
+/// A board as a 5x5 grid of numbers and whether they are marked
+type Grid = [[(Number, bool); 5]; 5];
+
+fn grid(bingo: &Bingo, board: usize) -> Grid {
+    let mut grid = [[(0, false); 5]; 5];
+    for (&n, &(x, y)) in &bingo.boards[board].numbers {
+        grid[y][x] = (n, false);
+    }
+    grid
+}
+
+fn has_won(grid: &Grid) -> bool {
+    let row = (0..5).any(|y| (0..5).all(|x| grid[y][x].1));
+    let col = (0..5).any(|x| (0..5).all(|y| grid[y][x].1));
+    row || col
+}
+
+fn score(grid: &Grid, number: Number) -> u128 {
+    let unmarked: u128 = grid
+        .iter()
+        .flatten()
+        .filter(|c| !c.1)
+        .map(|c| c.0 as u128)
+        .sum();
+    unmarked * number as u128
+}
+
+/// The score of every board as it wins, in the order that they win
+fn winners(bingo: &Bingo) -> Vec<u128> {
+    let mut grids: Vec<Grid> = (0..bingo.boards.len()).map(|i| grid(bingo, i)).collect();
+    let mut won = vec![false; grids.len()];
+    let mut scores = vec![];
+
+    for &number in &bingo.numbers {
+        for (i, grid) in grids.iter_mut().enumerate() {
+            for cell in grid.iter_mut().flatten() {
+                if cell.0 == number {
+                    cell.1 = true;
+                }
+            }
+
+            if !won[i] && has_won(grid) {
+                won[i] = true;
+                scores.push(score(grid, number));
+            }
+        }
+    }
+    scores
+}
+
+pub fn part1(bingo: &Bingo) -> Answer {
+    winners(bingo)[0].into()
+}
+
+pub fn part2(bingo: &Bingo) -> Answer {
+    (*winners(bingo).last().unwrap()).into()
+}
@@ -0,0 +1,194 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, ParseErrorKind, SolveError};
+use crate::grid::Grid;
+use crate::parsing::Source;
+use crate::solution::{Generator, Solution};
+use fastrand::Rng;
+use std::cmp::max;
+
+type Pos = (usize, usize);
+
+const DAY: u32 = 5;
+
+/// Width and height of the ocean floor. Every line must fit inside it.
+const GRID: usize = 1000;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day05;
+solution!(Day05);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line {
+    start: Pos,
+    end: Pos,
+}
+
+fn count_overlaps(lines: &[Line]) -> usize {
+    let mut points = Grid::new(GRID, GRID, 0u16);
+    for line in lines {
+        for p in line.points() {
+            // only whether a point is covered more than once matters
+            points[p] = points[p].saturating_add(1);
+        }
+    }
+
+    points.iter().filter(|&&count| count > 1).count()
+}
+
+impl Solution for Day05 {
+    type Input = Vec<Line>;
+
+    fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+        let source = Source::new(DAY, input);
+        let parse_pos = |s| source.pair(s, ",", |n| source.number_in(n, 0..GRID));
+
+        source
+            .lines()
+            .map(|line| {
+                let (start, end) = source.pair(line, " -> ", parse_pos)?;
+                let parsed = Line { start, end };
+
+                let dx = parsed.start.0.abs_diff(parsed.end.0);
+                let dy = parsed.start.1.abs_diff(parsed.end.1);
+                if dx != 0 && dy != 0 && dx != dy {
+                    // only horizontal, vertical and 45 degree lines are allowed
+                    return Err(source.error(line, ParseErrorKind::UnexpectedToken));
+                }
+
+                Ok(parsed)
+            })
+            .collect()
+    }
+
+    fn part1(lines: &Vec<Line>) -> Result<Answer, SolveError> {
+        let lines: Vec<Line> = lines
+            .iter()
+            .copied()
+            .filter(|line| line.start.0 == line.end.0 || line.start.1 == line.end.1)
+            .collect();
+
+        Ok(count_overlaps(&lines).into())
+    }
+
+    fn part2(lines: &Vec<Line>) -> Result<Answer, SolveError> {
+        Ok(count_overlaps(lines).into())
+    }
+}
+
+/// `size` is the number of lines. Lines are horizontal, vertical or diagonal at 45 degrees,
+/// and fit on the 1000x1000 grid.
+impl Generator for Day05 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let (x1, y1) = (rng.usize(..GRID), rng.usize(..GRID));
+                let (x2, y2) = match rng.u8(0..3) {
+                    0 => (rng.usize(..GRID), y1),
+                    1 => (x1, rng.usize(..GRID)),
+                    _ => {
+                        let (right, down) = (rng.bool(), rng.bool());
+                        let room_x = if right { GRID - 1 - x1 } else { x1 };
+                        let room_y = if down { GRID - 1 - y1 } else { y1 };
+                        let len = rng.usize(..=room_x.min(room_y));
+                        let x2 = if right { x1 + len } else { x1 - len };
+                        let y2 = if down { y1 + len } else { y1 - len };
+                        (x2, y2)
+                    }
+                };
+                format!("{},{} -> {},{}\n", x1, y1, x2, y2)
+            })
+            .collect()
+    }
+}
+
+impl Line {
+    fn points(&self) -> impl Iterator<Item = Pos> {
+        LineIter::new(self.start, self.end)
+    }
+}
+
+/// An iterator over all the points in a line
+struct LineIter {
+    x: isize,
+    y: isize,
+    dx: isize,
+    dy: isize,
+    steps: usize,
+}
+
+impl LineIter {
+    pub fn new(start: Pos, end: Pos) -> Self {
+        let dir = |s, e: usize| e.cmp(&s) as isize;
+
+        let (x1, y1) = (start.0 as isize, start.1 as isize);
+        let (x2, y2) = (end.0 as isize, end.1 as isize);
+        let diff_x = (x1 - x2).abs();
+        let diff_y = (y1 - y2).abs();
+        Self {
+            x: x1,
+            y: y1,
+            dx: dir(start.0, end.0),
+            dy: dir(start.1, end.1),
+            steps: max(diff_x, diff_y) as usize + 1,
+        }
+    }
+}
+
+impl Iterator for LineIter {
+    type Item = Pos;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.steps == 0 {
+            None
+        } else {
+            self.steps -= 1;
+
+            let p = (self.x as usize, self.y as usize);
+            self.x += self.dx;
+            self.y += self.dy;
+            Some(p)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, part1, part2, reference, Day05};
+    use crate::error::ParseErrorKind;
+    use crate::testing::check_against_reference;
+
+    #[test]
+    pub fn test_parse_error() {
+        let error = parse("0,9 -> 5,9\n8,0 => 0,8\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.kind, ParseErrorKind::MissingDelimiter(" -> "));
+
+        let error = parse("0,9 -> 5,x9\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+        assert_eq!(error.token, "x9");
+
+        let error = parse("0,9 -> 1000,9\n").unwrap_err();
+        assert_eq!((error.kind, error.column), (ParseErrorKind::OutOfRange, 8));
+
+        let error = parse("0,0 -> 2,1\n").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedToken);
+    }
+
+    #[test]
+    pub fn test_many_overlaps() {
+        // more lines through a point than its u16 cell can count
+        let input = "0,0 -> 2,0\n".repeat(70_000) + "0,0 -> 2,2\n";
+        assert_eq!(part1(&input).unwrap(), 3.into());
+        assert_eq!(part2(&input).unwrap(), 3.into());
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day05>(
+            |input| reference::part1(input),
+            |input| reference::part2(input),
+            30,
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use super::Line;
+use crate::answer::Answer;
+use std::collections::HashMap;
+
+fn count_overlaps<'a>(lines: impl Iterator<Item = &'a Line>) -> usize {
+    let mut covered: HashMap<(i64, i64), usize> = HashMap::new();
+    for line in lines {
+        let (x1, y1) = (line.start.0 as i64, line.start.1 as i64);
+        let (x2, y2) = (line.end.0 as i64, line.end.1 as i64);
+        let (dx, dy) = ((x2 - x1).signum(), (y2 - y1).signum());
+
+        let (mut x, mut y) = (x1, y1);
+        loop {
+            *covered.entry((x, y)).or_default() += 1;
+            if (x, y) == (x2, y2) {
+                break;
+            }
+            x += dx;
+            y += dy;
+        }
+    }
+    covered.values().filter(|&&count| count >= 2).count()
+}
+
+pub fn part1(lines: &[Line]) -> Answer {
+    let straight = lines
+        .iter()
+        .filter(|line| line.start.0 == line.end.0 || line.start.1 == line.end.1);
+    count_overlaps(straight).into()
+}
+
+pub fn part2(lines: &[Line]) -> Answer {
+    count_overlaps(lines.iter()).into()
+}
@@ -0,0 +1,149 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, ParseErrorKind, SolveError, StreamError};
+use crate::parsing::Source;
+use crate::solution::{Generator, Solution, Streamed, Streaming};
+use fastrand::Rng;
+use std::io::{self, BufRead};
+
+type FishCount = u64;
+type School = [FishCount; 9];
+
+const DAY: u32 = 6;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day06;
+solution!(Day06);
+
+/// The number of fish after DAYS days
+///
+/// Counted in a `u128`, which holds the fish after 256 days of any school that fits a [School].
+fn simulate<const DAYS: usize>(school: School) -> u128 {
+    let mut fish = school.map(u128::from);
+    for _day in 0..DAYS {
+        fish.rotate_left(1);
+        fish[6] += fish[8]; // yesss... breed my pretties!
+    }
+
+    fish.iter().sum()
+}
+
+/// Count a fish with the given timer, which is a piece of the `source`
+fn add_fish(school: &mut School, source: Source, timer: &str) -> Result<(), ParseError> {
+    let n: usize = source.number(timer)?;
+    let count = school
+        .get_mut(n)
+        .ok_or_else(|| source.error(timer, ParseErrorKind::UnexpectedToken))?;
+    *count += 1;
+    Ok(())
+}
+
+impl Solution for Day06 {
+    type Input = School;
+
+    fn parse(input: &str) -> Result<School, ParseError> {
+        let source = Source::new(DAY, input);
+        let mut school = [0; 9];
+        for added in source.separated(input, ',', |timer| add_fish(&mut school, source, timer)) {
+            added?;
+        }
+        Ok(school)
+    }
+
+    fn part1(&fish: &School) -> Result<Answer, SolveError> {
+        Ok(simulate::<80>(fish).into())
+    }
+
+    fn part2(&fish: &School) -> Result<Answer, SolveError> {
+        Ok(simulate::<256>(fish).into())
+    }
+}
+
+/// The timers are read one at a time, so even a single line of them doesn't have to fit in memory
+impl Streaming for Day06 {
+    fn stream(input: &mut dyn BufRead) -> Result<Streamed, StreamError> {
+        let mut school = [0; 9];
+        let mut field = vec![];
+        let (mut line, mut column) = (1, 1);
+
+        loop {
+            field.clear();
+            input.read_until(b',', &mut field)?;
+            let last = field.pop_if(|&mut byte| byte == b',').is_none();
+            let text = std::str::from_utf8(&field)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            // the same as Source::separated
+            add_fish(&mut school, Source::new(DAY, text), text.trim())
+                .map_err(|e| e.shifted(line, column))?;
+
+            if last {
+                break;
+            }
+            match text.rfind('\n') {
+                Some(i) => {
+                    line += text.matches('\n').count();
+                    column = text[i + 1..].chars().count() + 2;
+                }
+                None => column += text.chars().count() + 1,
+            }
+        }
+
+        Ok(Streamed {
+            part1: Some(Ok(simulate::<80>(school).into())),
+            part2: Some(Ok(simulate::<256>(school).into())),
+        })
+    }
+}
+
+/// `size` is the number of fish
+impl Generator for Day06 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let timers: Vec<String> = (0..size.max(1))
+            .map(|_| rng.u8(1..=5).to_string())
+            .collect();
+        timers.join(",") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, reference, simulate, Day06};
+    use crate::error::{ParseErrorKind, StreamError};
+    use crate::solution::Streaming;
+    use crate::testing::{check_against_reference, check_streaming};
+
+    #[test]
+    pub fn test_parse_error() {
+        let error = parse("3,4,12,1,2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedToken);
+    }
+
+    #[test]
+    pub fn test_huge_school() {
+        // a school this size used to overflow the u64 the fish were counted in
+        let one = simulate::<256>([0, 1, 0, 0, 0, 0, 0, 0, 0]);
+        let many = simulate::<256>([0, u64::MAX, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(many, one * u64::MAX as u128);
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day06>(reference::part1, reference::part2, 30);
+    }
+
+    #[test]
+    pub fn test_stream() {
+        check_streaming::<Day06>();
+
+        let streamed = Day06::stream(&mut " 3,4,3,1,2\r\n".as_bytes()).unwrap();
+        assert_eq!(streamed.part1, Some(Ok(5934u64.into())));
+
+        match Day06::stream(&mut "3,4,12,1,2\n".as_bytes()) {
+            Err(StreamError::Parse(error)) => assert_eq!((error.line, error.column), (1, 5)),
+            result => panic!("expected a parse error, got {:?}", result),
+        }
+    }
+}
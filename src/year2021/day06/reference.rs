@@ -0,0 +1,55 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use super::School;
+use crate::answer::Answer;
+use std::collections::HashMap;
+
+/// Simulate every fish on its own
+pub fn part1(school: &School) -> Answer {
+    let mut fish: Vec<u8> = vec![];
+    for (timer, &count) in school.iter().enumerate() {
+        fish.extend(std::iter::repeat_n(timer as u8, count as usize));
+    }
+
+    for _day in 0..80 {
+        let mut born = 0;
+        for timer in fish.iter_mut() {
+            if *timer == 0 {
+                *timer = 6;
+                born += 1;
+            } else {
+                *timer -= 1;
+            }
+        }
+        fish.extend(std::iter::repeat_n(8, born));
+    }
+
+    fish.len().into()
+}
+
+/// Too many fish to simulate one by one, so follow each fish and its young down the days,
+/// remembering how many fish a single fish ends up as
+pub fn part2(school: &School) -> Answer {
+    let mut known = HashMap::new();
+    let mut total = 0u128;
+    for (timer, &count) in school.iter().enumerate() {
+        total += count as u128 * descendants(timer, 256, &mut known);
+    }
+    total.into()
+}
+
+/// How many fish a fish with `timer` is after `days`, counting itself
+fn descendants(timer: usize, days: usize, known: &mut HashMap<(usize, usize), u128>) -> u128 {
+    if days <= timer {
+        return 1;
+    }
+    if let Some(&count) = known.get(&(timer, days)) {
+        return count;
+    }
+
+    // the day its timer runs out it resets to 6 and has a child at 8
+    let left = days - timer - 1;
+    let count = descendants(6, left, known) + descendants(8, left, known);
+    known.insert((timer, days), count);
+    count
+}
@@ -0,0 +1,136 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, SolveError};
+use crate::parsing::Source;
+use crate::solution::{Generator, Solution};
+use fastrand::Rng;
+use num_bigint::BigInt;
+use std::cmp::min;
+
+const DAY: u32 = 7;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day07;
+solution!(Day07);
+
+impl Solution for Day07 {
+    type Input = Vec<i64>;
+
+    /// Parse to a sorted list numbers
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        let source = Source::new(DAY, input);
+        let mut crabs: Vec<_> = source
+            .separated(input, ',', |n| source.number(n))
+            .collect::<Result<_, _>>()?;
+        crabs.sort_unstable();
+        Ok(crabs)
+    }
+
+    /// Works in an `i128`, where the fuel can't overflow for any input that fits in memory
+    fn part1(crabs: &Vec<i64>) -> Result<Answer, SolveError> {
+        let min_crab = crabs.iter().copied().min().unwrap() as i128;
+        let max_crab = crabs.iter().copied().max().unwrap() as i128;
+        let mid_crab = (min_crab + max_crab) / 2;
+
+        // applying the fuel function over the span of crabs yields a polynomial
+        // let's solve it
+        let (x1, y1) = (min_crab as f64, fuel_to_move_to(crabs, min_crab) as f64);
+        let (x2, y2) = (mid_crab as f64, fuel_to_move_to(crabs, mid_crab) as f64);
+        let (x3, y3) = (max_crab as f64, fuel_to_move_to(crabs, max_crab) as f64);
+        let a = (x1 * (y3 - y2) + x2 * (y1 - y3) + x3 * (y2 - y1))
+            / ((x1 - x2) * (x1 - x3) * (x2 - x3));
+        let b = (y2 - y1) / (x2 - x1) - a * (x1 + x2);
+        let function_min = ((-b / 2.0 / a) as i128).clamp(min_crab, max_crab);
+
+        fn fuel_to_move_to(crabs: &[i64], to: i128) -> i128 {
+            crabs.iter().map(|&from| (from as i128 - to).abs()).sum()
+        }
+
+        /// take elements from the iterator while they are decreasing, then return the smallest
+        fn scan(i: impl Iterator<Item = i128>, crabs: &[i64]) -> i128 {
+            let mut i = i.map(|pos| fuel_to_move_to(crabs, pos));
+            let mut last = i.next().unwrap();
+
+            for fuel in i {
+                if fuel < last {
+                    last = fuel;
+                } else {
+                    break;
+                }
+            }
+
+            last
+        }
+
+        // the polynomial is slightly inaccurate,
+        // so we look at the nearby values to find the smallest
+        let left = (1..).map(|i| function_min - i);
+        let right = function_min..;
+        Ok(min(scan(left, crabs), scan(right, crabs)).into())
+    }
+
+    /// The fuel of a single crab fits a `u128`, but the total needs a [BigInt]
+    fn part2(crabs: &Vec<i64>) -> Result<Answer, SolveError> {
+        let crab_average =
+            crabs.iter().map(|&crab| crab as i128).sum::<i128>() / crabs.len() as i128;
+
+        fn fuel_to_move_to(crabs: &[i64], to: i128) -> BigInt {
+            crabs
+                .iter()
+                .map(|&from| (from as i128 - to).unsigned_abs())
+                .map(|steps| BigInt::from(steps * (steps + 1) / 2))
+                .sum()
+        }
+
+        // account for rounding errors
+        let fuel = [crab_average, crab_average + 1]
+            .map(|pos| fuel_to_move_to(crabs, pos))
+            .into_iter()
+            .min()
+            .unwrap();
+        Ok(fuel.into())
+    }
+}
+
+/// `size` is the number of crabs, which are spread over about twice as many positions
+impl Generator for Day07 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        let spread = 2 * size as i64;
+        let crabs: Vec<String> = (0..size).map(|_| rng.i64(0..spread).to_string()).collect();
+        crabs.join(",") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, reference, Day07};
+    use crate::solution::Solution;
+    use crate::testing::check_against_reference;
+
+    #[test]
+    pub fn test_parse_error() {
+        let error = parse("16,1,2,,0\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+        assert_eq!(error.token, "");
+    }
+
+    #[test]
+    pub fn test_far_apart() {
+        // the crabs meet at 0, each of them i64::MAX steps away
+        let crabs = parse(&format!("{},{}\n", -i64::MAX, i64::MAX)).unwrap();
+        let steps = i64::MAX as u128;
+        assert_eq!(Day07::part1(&crabs), Ok((2 * steps).into()));
+        assert_eq!(Day07::part2(&crabs), Ok((steps * (steps + 1)).into()));
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day07>(
+            |input| reference::part1(input),
+            |input| reference::part2(input),
+            200,
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use crate::answer::Answer;
+
+/// Try every position and pick the cheapest
+fn cheapest(crabs: &[i64], cost: impl Fn(i64) -> i64) -> i64 {
+    let min = *crabs.iter().min().unwrap();
+    let max = *crabs.iter().max().unwrap();
+    (min..=max)
+        .map(|to| crabs.iter().map(|&from| cost((from - to).abs())).sum())
+        .min()
+        .unwrap()
+}
+
+pub fn part1(crabs: &[i64]) -> Answer {
+    cheapest(crabs, |steps| steps).into()
+}
+
+pub fn part2(crabs: &[i64]) -> Answer {
+    cheapest(crabs, |steps| steps * (steps + 1) / 2).into()
+}
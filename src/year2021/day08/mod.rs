@@ -0,0 +1,387 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, ParseErrorKind, SolveError, StreamError};
+use crate::parsing::Source;
+use crate::solution::{for_each_line, Generator, Solution, Streamed, Streaming};
+use fastrand::Rng;
+use std::array;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+const DAY: u32 = 8;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day08;
+solution!(Day08);
+
+#[derive(Debug)]
+pub struct Entry {
+    patterns: [Seg; 10],
+    output: [Seg; 4],
+}
+
+type Seg = [bool; 7];
+
+#[repr(usize)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum Signal {
+    #[default]
+    A = 0,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+}
+use Signal::*;
+
+impl From<usize> for Signal {
+    fn from(i: usize) -> Self {
+        [A, B, C, D, E, F, G][i]
+    }
+}
+
+const LEN_SEG_1: usize = 2;
+const LEN_SEG_4: usize = 4;
+const LEN_SEG_7: usize = 3;
+const LEN_SEG_8: usize = 7;
+
+/// The segments that are lit for each digit, when the wiring isn't scrambled
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+fn active_segments(seg: &Seg) -> usize {
+    seg.iter().filter(|&signal| *signal).count()
+}
+
+/// Parse a segment, or return the first unexpected char
+fn parse_segment(input: &str) -> Result<Seg, &str> {
+    let mut seg = Seg::default();
+
+    for (i, c) in input.char_indices() {
+        let signal = match c {
+            'a' => A,
+            'b' => B,
+            'c' => C,
+            'd' => D,
+            'e' => E,
+            'f' => F,
+            'g' => G,
+            _ => return Err(&input[i..i + c.len_utf8()]),
+        };
+        seg[signal as usize] = true;
+    }
+
+    Ok(seg)
+}
+
+/// Parse exactly N whitespace-separated segments from `segs`, a piece of the `source`
+fn parse_segments<const N: usize>(source: Source, segs: &str) -> Result<[Seg; N], ParseError> {
+    let error = |token: &str, kind| source.error(token, kind);
+
+    let parsed: Vec<Seg> = segs
+        .split_whitespace()
+        .map(|seg| parse_segment(seg).map_err(|c| error(c, ParseErrorKind::UnexpectedToken)))
+        .collect::<Result<_, _>>()?;
+
+    let found = parsed.len();
+    parsed
+        .try_into()
+        .map_err(|_| error(segs, ParseErrorKind::WrongLength { expected: N, found }))
+}
+
+/// The digit shown by each segment, when the wiring isn't scrambled
+fn seg_numbers() -> HashMap<Seg, usize> {
+    DIGIT_SEGMENTS
+        .iter()
+        .enumerate()
+        .map(|(digit, s)| (parse_segment(s).expect("invalid segment constant"), digit))
+        .collect()
+}
+
+/// Figure out the signal wiring of an entry and decode its output value
+///
+/// None if no wiring turns the patterns into the ten digits.
+fn decode_entry(entry: &Entry, seg_numbers: &HashMap<Seg, usize>) -> Option<usize> {
+    let seg_xor = |seg1: Seg, seg2: Seg| -> Seg { array::from_fn(|i| seg1[i] != seg2[i]) };
+
+    fn sigs<const N: usize>(seg: Seg) -> Option<[Signal; N]> {
+        let sigs = seg
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, active)| active)
+            .map(|(i, _)| i.into());
+        collect_array(sigs)
+    }
+
+    fn find_segs_w_len<const N: usize>(entry: &Entry, len: usize) -> Option<[Seg; N]> {
+        let valid = entry
+            .patterns
+            .iter()
+            .copied()
+            .filter(|entry| active_segments(entry) == len);
+        collect_array(valid)
+    }
+
+    let [seg_1] = find_segs_w_len(entry, LEN_SEG_1)?;
+    let [seg_4] = find_segs_w_len(entry, LEN_SEG_4)?;
+    let [seg_7] = find_segs_w_len(entry, LEN_SEG_7)?;
+    let [seg_8] = find_segs_w_len(entry, LEN_SEG_8)?;
+    let segs_069: [Seg; 3] = find_segs_w_len(entry, 6)?;
+
+    let [repr_a] = sigs(seg_xor(seg_1, seg_7))?;
+
+    let repr_cf: [Signal; 2] = sigs(seg_1)?;
+    let (seg_6, repr_c, repr_f) = segs_069.into_iter().find_map(|seg| {
+        let contains_c_and_f = repr_cf.into_iter().all(|c_or_f| seg[c_or_f as usize]);
+
+        if contains_c_and_f {
+            None
+        } else {
+            // find out which one is c and which one is f
+            Some(if seg[repr_cf[0] as usize] {
+                let [repr_f, repr_c] = repr_cf;
+                (seg, repr_c, repr_f)
+            } else {
+                let [repr_c, repr_f] = repr_cf;
+                (seg, repr_c, repr_f)
+            })
+        }
+    })?;
+
+    let repr_bd: [Signal; 2] = sigs(seg_xor(seg_1, seg_4))?;
+    let segs_09: [Seg; 2] = collect_array(segs_069.into_iter().filter(|&seg| seg != seg_6))?;
+
+    let (seg_0, repr_d, repr_b) = segs_09.into_iter().find_map(|seg| {
+        let contains_b_and_d = repr_bd.into_iter().all(|b_or_d| seg[b_or_d as usize]);
+
+        if contains_b_and_d {
+            None
+        } else {
+            // find out which one is b and which one is d
+            Some(if seg[repr_bd[0] as usize] {
+                let [repr_b, repr_d] = repr_bd;
+                (seg, repr_d, repr_b)
+            } else {
+                let [repr_d, repr_b] = repr_bd;
+                (seg, repr_d, repr_b)
+            })
+        }
+    })?;
+
+    let [seg_9] = collect_array(segs_09.into_iter().filter(|&seg| seg != seg_0))?;
+
+    let [repr_e] = sigs(seg_xor(seg_9, seg_8))?;
+
+    let mut signal_map: [Signal; 7] = [G; 7];
+    signal_map[repr_a as usize] = A;
+    signal_map[repr_b as usize] = B;
+    signal_map[repr_c as usize] = C;
+    signal_map[repr_d as usize] = D;
+    signal_map[repr_e as usize] = E;
+    signal_map[repr_f as usize] = F;
+    //signal_map[repr_g as usize] = G;
+
+    let decode = |seg: &Seg| {
+        let mut unjarbled_seg = Seg::default();
+        for (i, &active) in seg.iter().enumerate() {
+            if active {
+                unjarbled_seg[signal_map[i] as usize] = true;
+            }
+        }
+        seg_numbers.get(&unjarbled_seg).copied()
+    };
+
+    // a wrong wiring could still happen to decode the output, so check it against the patterns
+    let mut seen = [false; 10];
+    for pattern in &entry.patterns {
+        seen[decode(pattern)?] = true;
+    }
+    if seen.contains(&false) {
+        return None;
+    }
+
+    entry
+        .output
+        .iter()
+        .try_fold(0, |value, seg| Some(value * 10 + decode(seg)?))
+}
+
+/// Parse an entry from a line of the `source`
+fn parse_entry(source: Source, line: &str) -> Result<Entry, ParseError> {
+    let (patterns, output) = source.split_once(line, " | ")?;
+    Ok(Entry {
+        patterns: parse_segments(source, patterns)?,
+        output: parse_segments(source, output)?,
+    })
+}
+
+/// Part 2 has no answer if any entry can't be decoded
+fn undecodable() -> SolveError {
+    SolveError::new(
+        DAY,
+        2,
+        "no wiring turns the patterns of an entry into the ten digits",
+    )
+}
+
+/// Number of output digits that can be told apart by how many segments they light up
+fn easy_digits(entry: &Entry) -> usize {
+    entry
+        .output
+        .iter()
+        .map(active_segments)
+        .filter(|num_signals| [LEN_SEG_1, LEN_SEG_7, LEN_SEG_4, LEN_SEG_8].contains(num_signals))
+        .count()
+}
+
+impl Solution for Day08 {
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> Result<Vec<Entry>, ParseError> {
+        let source = Source::new(DAY, input);
+        source
+            .lines()
+            .map(|line| parse_entry(source, line))
+            .collect()
+    }
+
+    fn part1(entries: &Vec<Entry>) -> Result<Answer, SolveError> {
+        Ok(entries.iter().map(easy_digits).sum::<usize>().into())
+    }
+
+    fn part2(entries: &Vec<Entry>) -> Result<Answer, SolveError> {
+        let seg_numbers = seg_numbers();
+
+        entries
+            .iter()
+            .map(|entry| decode_entry(entry, &seg_numbers))
+            .sum::<Option<usize>>()
+            .map(Answer::from)
+            .ok_or_else(undecodable)
+    }
+}
+
+impl Streaming for Day08 {
+    fn stream(input: &mut dyn BufRead) -> Result<Streamed, StreamError> {
+        let seg_numbers = seg_numbers();
+        let (mut part1, mut part2) = (0usize, Some(0usize));
+        for_each_line(input, |line| {
+            let entry = parse_entry(Source::new(DAY, line), line)?;
+            part1 += easy_digits(&entry);
+            part2 = part2
+                .zip(decode_entry(&entry, &seg_numbers))
+                .map(|(sum, value)| sum + value);
+            Ok(())
+        })?;
+
+        Ok(Streamed {
+            part1: Some(Ok(part1.into())),
+            part2: Some(part2.map(Answer::from).ok_or_else(undecodable)),
+        })
+    }
+}
+
+/// `size` is the number of entries, each with its own scrambled wiring
+impl Generator for Day08 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut wiring: Vec<char> = "abcdefg".chars().collect();
+        let mut digits: Vec<usize> = (0..10).collect();
+
+        (0..size)
+            .map(|_| {
+                rng.shuffle(&mut wiring);
+                rng.shuffle(&mut digits);
+                let shown: Vec<usize> = (0..4).map(|_| rng.usize(..10)).collect();
+
+                let mut scramble = |digit: usize| {
+                    let mut signals: Vec<char> = DIGIT_SEGMENTS[digit]
+                        .bytes()
+                        .map(|b| wiring[(b - b'a') as usize])
+                        .collect();
+                    rng.shuffle(&mut signals);
+                    signals.into_iter().collect::<String>()
+                };
+
+                let patterns: Vec<String> = digits.iter().map(|&d| scramble(d)).collect();
+                let output: Vec<String> = shown.iter().map(|&d| scramble(d)).collect();
+                format!("{} | {}\n", patterns.join(" "), output.join(" "))
+            })
+            .collect()
+    }
+}
+
+/// Collect exactly N items, or None if the iterator has more or fewer
+fn collect_array<T: Default + Copy, const N: usize>(
+    mut iter: impl Iterator<Item = T>,
+) -> Option<[T; N]> {
+    let mut out = [T::default(); N];
+    for slot in out.iter_mut() {
+        *slot = iter.next()?;
+    }
+    iter.next().is_none().then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, reference, Day08};
+    use crate::error::{ParseErrorKind, StreamError};
+    use crate::solution::{Solution, Streaming};
+    use crate::testing::{check_against_reference, check_streaming};
+
+    #[test]
+    pub fn test_parse_error() {
+        let input = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf\n\
+                     acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcaxb cdfeb cdbaf";
+        let error = parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 71));
+        assert_eq!(error.token, "x");
+
+        let input = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb";
+        let error = parse(input).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::WrongLength {
+                expected: 4,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_no_solution() {
+        // well formed, but every pattern lights the same two segments
+        let input = "ab ab ab ab ab ab ab ab ab ab | ab ab ab ab\n";
+        let entries = parse(input).unwrap();
+        assert_eq!(Day08::part1(&entries), Ok(4.into()));
+        assert_eq!(Day08::part2(&entries).unwrap_err().part, 2);
+
+        let streamed = Day08::stream(&mut input.as_bytes()).unwrap();
+        assert_eq!(streamed.part1, Some(Ok(4.into())));
+        assert_eq!(streamed.part2.unwrap().unwrap_err().part, 2);
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day08>(
+            |input| reference::part1(input),
+            |input| reference::part2(input),
+            30,
+        );
+    }
+
+    #[test]
+    pub fn test_stream() {
+        check_streaming::<Day08>();
+
+        let input = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf\n\
+                     acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcaxb cdfeb cdbaf";
+        match Day08::stream(&mut input.as_bytes()) {
+            Err(StreamError::Parse(error)) => assert_eq!((error.line, error.column), (2, 71)),
+            result => panic!("expected a parse error, got {:?}", result),
+        }
+    }
+}
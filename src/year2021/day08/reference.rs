@@ -0,0 +1,66 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use super::{active_segments, Entry, Seg, DIGIT_SEGMENTS};
+use crate::answer::Answer;
+
+pub fn part1(entries: &[Entry]) -> Answer {
+    let unique_lengths = [2, 3, 4, 7];
+    let count = entries
+        .iter()
+        .flat_map(|entry| entry.output.iter())
+        .filter(|seg| unique_lengths.contains(&active_segments(seg)))
+        .count();
+    count.into()
+}
+
+/// Every ordering of the 7 wires
+fn permutations() -> Vec<[usize; 7]> {
+    let mut perms = vec![];
+    let mut perm = [0; 7];
+    fn fill(i: usize, perm: &mut [usize; 7], used: &mut [bool; 7], perms: &mut Vec<[usize; 7]>) {
+        if i == 7 {
+            perms.push(*perm);
+            return;
+        }
+        for wire in 0..7 {
+            if !used[wire] {
+                used[wire] = true;
+                perm[i] = wire;
+                fill(i + 1, perm, used, perms);
+                used[wire] = false;
+            }
+        }
+    }
+    fill(0, &mut perm, &mut [false; 7], &mut perms);
+    perms
+}
+
+/// The digit shown by a segment once the wires are untangled by `wiring`, if any
+fn digit(seg: &Seg, wiring: &[usize; 7]) -> Option<usize> {
+    let mut lit = String::new();
+    for segment in 0..7 {
+        if (0..7).any(|wire| seg[wire] && wiring[wire] == segment) {
+            lit.push((b'a' + segment as u8) as char);
+        }
+    }
+    DIGIT_SEGMENTS.iter().position(|&digit| digit == lit)
+}
+
+/// Try every wiring until one makes sense of all the patterns
+pub fn part2(entries: &[Entry]) -> Answer {
+    let permutations = permutations();
+    let mut sum = 0;
+    for entry in entries {
+        let wiring = permutations
+            .iter()
+            .find(|wiring| entry.patterns.iter().all(|p| digit(p, wiring).is_some()))
+            .unwrap();
+
+        let mut value = 0;
+        for seg in &entry.output {
+            value = value * 10 + digit(seg, wiring).unwrap();
+        }
+        sum += value;
+    }
+    sum.into()
+}
@@ -0,0 +1,192 @@
+use crate::answer::Answer;
+use crate::error::{ParseError, SolveError};
+use crate::grid::{Coord, Grid};
+use crate::solution::{Generator, Solution};
+use fastrand::Rng;
+use hashers::fx_hash::FxHasher;
+use std::collections::VecDeque;
+use std::hash::BuildHasherDefault;
+type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasherDefault<FxHasher>>;
+
+type Height = u8;
+
+const DAY: u32 = 9;
+
+#[cfg(test)]
+mod reference;
+
+pub struct Day09;
+solution!(Day09);
+
+fn neighbors(coord: Coord, map: &Grid<Height>) -> impl Iterator<Item = Height> + '_ {
+    map.neighbors4(coord).map(|n| map[n])
+}
+
+impl Solution for Day09 {
+    type Input = Grid<Height>;
+
+    fn parse(input: &str) -> Result<Grid<Height>, ParseError> {
+        Grid::parse_digits(DAY, input)
+    }
+
+    fn part1(map: &Grid<Height>) -> Result<Answer, SolveError> {
+        let risk = map
+            .coords()
+            .filter_map(|coord| {
+                let point = map[coord];
+                neighbors(coord, map)
+                    .all(|neighbor| neighbor > point)
+                    .then_some(point)
+            })
+            .map(|point| (point + 1) as u64)
+            .sum::<u64>();
+        Ok(risk.into())
+    }
+
+    fn part2(map: &Grid<Height>) -> Result<Answer, SolveError> {
+        #[derive(Clone, Copy)]
+        enum Flow {
+            LowPoint,
+            Into(Coord),
+            NotABasin,
+        }
+
+        let mut flow_map: Grid<Option<Flow>> = Grid::new(map.width(), map.height(), None);
+
+        fn determine_flow(
+            coord: Coord,
+            map: &Grid<Height>,
+            flow_map: &mut Grid<Option<Flow>>,
+        ) -> Flow {
+            if let Some(flow) = flow_map[coord] {
+                return flow;
+            }
+
+            // I'll have a #9 large, extra dip.
+            if map[coord] == 9 {
+                flow_map[coord] = Some(Flow::NotABasin);
+                return Flow::NotABasin;
+            }
+
+            let point = map[coord];
+            let lowest_neighbor = map
+                .neighbors4(coord)
+                .filter(|&n| map[n] < point)
+                .min_by_key(|&n| map[n]);
+
+            let flow = match lowest_neighbor {
+                Some(neighbor) => match determine_flow(neighbor, map, flow_map) {
+                    Flow::Into(lowest) => Flow::Into(lowest),
+                    Flow::LowPoint => Flow::Into(neighbor),
+                    Flow::NotABasin => Flow::LowPoint,
+                },
+                None => Flow::LowPoint,
+            };
+
+            flow_map[coord] = Some(flow);
+            flow
+        }
+
+        let mut basins: HashMap<Coord, u64> = HashMap::default();
+        for coord in map.coords() {
+            let flow = determine_flow(coord, map, &mut flow_map);
+
+            let lowest = match flow {
+                Flow::LowPoint => coord,
+                Flow::Into(lowest) => lowest,
+                Flow::NotABasin => continue,
+            };
+            *basins.entry(lowest).or_default() += 1;
+        }
+
+        let mut basins: Vec<u64> = basins.values().copied().collect();
+        if basins.len() < 3 {
+            return Err(SolveError::new(DAY, 2, "there are fewer than three basins"));
+        }
+        basins.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(basins[0..3].iter().copied().product::<u64>().into())
+    }
+}
+
+/// Breadth-first search from every low point at once over the cells where `open` is true
+///
+/// Gives every reached cell its distance to the closest low point, and the index of that low
+/// point. Ties go to the lowest index.
+fn flood(
+    low_points: &[Coord],
+    size: usize,
+    open: impl Fn(Coord) -> bool,
+) -> Grid<Option<(usize, usize)>> {
+    let mut cells = Grid::new(size, size, None);
+    let mut queue = VecDeque::new();
+    for (i, &coord) in low_points.iter().enumerate() {
+        cells[coord] = Some((0, i));
+        queue.push_back(coord);
+    }
+
+    while let Some(coord) = queue.pop_front() {
+        let (dist, owner) = cells[coord].unwrap();
+        for n in cells.neighbors4(coord) {
+            match &mut cells[n] {
+                cell @ None if open(n) => {
+                    *cell = Some((dist + 1, owner));
+                    queue.push_back(n);
+                }
+                Some((d, other)) if *d == dist + 1 => *other = owner.min(*other),
+                _ => {}
+            }
+        }
+    }
+    cells
+}
+
+/// `size` is the width and height of the heightmap, at least 12 to fit a few basins. The map is
+/// split into basins by walls of 9s, and every point in a basin flows down to the single low point
+/// of the basin.
+impl Generator for Day09 {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const SPACING: usize = 6;
+        let size = size.max(2 * SPACING);
+
+        // low points on a jittered grid, far enough apart that none of them end up in a wall
+        let cells = size / SPACING;
+        let low_points: Vec<Coord> = (0..cells * cells)
+            .map(|i| {
+                let (x, y) = (i % cells * SPACING, i / cells * SPACING);
+                (x + rng.usize(..3), y + rng.usize(..3))
+            })
+            .collect();
+
+        // every point belongs to the closest low point, and where two basins meet the one with
+        // the higher index gets a wall
+        let owners = flood(&low_points, size, |_| true);
+        let owner = |coord: Coord| owners[coord].map(|(_, owner)| owner);
+        let wall = |coord: Coord| owners.neighbors4(coord).any(|n| owner(n) < owner(coord));
+
+        // the height of a point is its distance to the low point, so that there is always a
+        // lower neighbor to flow into
+        flood(&low_points, size, |coord| !wall(coord))
+            .map(|cell| match cell {
+                Some((dist, _)) if *dist < 9 => *dist as Height,
+                _ => 9,
+            })
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, reference, Day09};
+    use crate::testing::check_against_reference;
+
+    #[test]
+    pub fn test_parse_error() {
+        let error = parse("2199\n39x7\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    pub fn test_reference() {
+        check_against_reference::<Day09>(reference::part1, reference::part2, 30);
+    }
+}
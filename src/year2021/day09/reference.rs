@@ -0,0 +1,66 @@
+//! Slow but obviously correct solutions, to test the real ones against
+
+use super::Height;
+use crate::answer::Answer;
+use crate::grid::Grid;
+
+fn neighbors(map: &Grid<Height>, x: usize, y: usize) -> Vec<(usize, usize)> {
+    let mut neighbors = vec![];
+    if x > 0 {
+        neighbors.push((x - 1, y));
+    }
+    if x + 1 < map.width() {
+        neighbors.push((x + 1, y));
+    }
+    if y > 0 {
+        neighbors.push((x, y - 1));
+    }
+    if y + 1 < map.height() {
+        neighbors.push((x, y + 1));
+    }
+    neighbors
+}
+
+pub fn part1(map: &Grid<Height>) -> Answer {
+    let mut risk = 0u64;
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let height = map[(x, y)];
+            if neighbors(map, x, y).iter().all(|&n| map[n] > height) {
+                risk += height as u64 + 1;
+            }
+        }
+    }
+    risk.into()
+}
+
+/// Basins are the areas between the 9s, so flood fill each of them
+pub fn part2(map: &Grid<Height>) -> Answer {
+    let mut seen = Grid::new(map.width(), map.height(), false);
+    let mut sizes = vec![];
+
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            if seen[(x, y)] || map[(x, y)] == 9 {
+                continue;
+            }
+
+            let mut size = 0u64;
+            let mut stack = vec![(x, y)];
+            seen[(x, y)] = true;
+            while let Some((x, y)) = stack.pop() {
+                size += 1;
+                for n in neighbors(map, x, y) {
+                    if !seen[n] && map[n] != 9 {
+                        seen[n] = true;
+                        stack.push(n);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+    }
+
+    sizes.sort_unstable();
+    sizes.iter().rev().take(3).product::<u64>().into()
+}
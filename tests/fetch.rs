@@ -0,0 +1,99 @@
+use aoc::config::Config;
+use aoc::fetch::{Client, FetchError, InputCache};
+use aoc::mock_server::MockServer;
+use tempfile::TempDir;
+
+const SESSION: &str = "abc123";
+const INPUT: &str = "199\n200\n208\n";
+
+fn setup(session: Option<&str>) -> (MockServer, Client, InputCache, TempDir) {
+    let server = MockServer::start(SESSION).unwrap();
+    server.add_input(2021, 1, INPUT);
+
+    let dir = TempDir::new().unwrap();
+    let config = Config {
+        url: server.url(),
+        session: session.map(str::to_string),
+        cache: dir.path().to_owned(),
+        answers: dir.path().join("answers.toml"),
+    };
+
+    let client = Client::new(&config);
+    let cache = InputCache::new(&config.cache);
+    (server, client, cache, dir)
+}
+
+#[test]
+fn fetch_into_cache() {
+    let (server, client, cache, _dir) = setup(Some(SESSION));
+
+    assert_eq!(cache.get(2021, 1).unwrap(), None);
+    assert_eq!(cache.get_or_fetch(&client, 2021, 1).unwrap(), INPUT);
+    assert_eq!(cache.get(2021, 1).unwrap().as_deref(), Some(INPUT));
+
+    // the second time it should come from the cache
+    assert_eq!(cache.get_or_fetch(&client, 2021, 1).unwrap(), INPUT);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2021/day/1/input");
+    assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+}
+
+#[test]
+fn session_copied_from_browser() {
+    let (_server, client, _cache, _dir) = setup(Some("session=abc123;"));
+    assert_eq!(client.input(2021, 1).unwrap(), INPUT);
+}
+
+#[test]
+fn missing_session() {
+    let (server, client, cache, _dir) = setup(None);
+
+    let error = cache.get_or_fetch(&client, 2021, 1).unwrap_err();
+    assert!(matches!(error, FetchError::NoSession));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn wrong_session() {
+    let (_server, client, cache, _dir) = setup(Some("wrong"));
+
+    let error = cache.get_or_fetch(&client, 2021, 1).unwrap_err();
+    assert!(matches!(error, FetchError::Status { status: 400, .. }));
+    assert_eq!(cache.get(2021, 1).unwrap(), None);
+}
+
+#[test]
+fn missing_input() {
+    let (_server, client, _cache, _dir) = setup(Some(SESSION));
+
+    let error = client.input(2021, 25).unwrap_err();
+    assert!(matches!(error, FetchError::Status { status: 404, .. }));
+}
+
+#[test]
+fn fetch_page_into_cache() {
+    let (server, client, cache, _dir) = setup(Some(SESSION));
+    server.add_page(2021, 1, "<article><em>7</em></article>");
+
+    let page = cache.page_or_fetch(&client, 2021, 1, false).unwrap();
+    assert_eq!(
+        cache.get_page(2021, 1).unwrap().as_deref(),
+        Some(page.as_str())
+    );
+
+    // part 2 shows up once part 1 is solved, so refreshing has to skip the cache
+    server.add_page(
+        2021,
+        1,
+        "<article><em>7</em></article><article><em>5</em></article>",
+    );
+    assert_eq!(cache.page_or_fetch(&client, 2021, 1, false).unwrap(), page);
+    assert!(cache
+        .page_or_fetch(&client, 2021, 1, true)
+        .unwrap()
+        .ends_with("<em>5</em></article>"));
+
+    let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(paths, vec!["/2021/day/1", "/2021/day/1"]);
+}
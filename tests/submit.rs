@@ -0,0 +1,105 @@
+use aoc::config::Config;
+use aoc::fetch::Client;
+use aoc::mock_server::MockServer;
+use aoc::submit::{submit, Outcome, Response, SubmitError, SubmitLog};
+use std::time::Duration;
+use tempfile::TempDir;
+
+const SESSION: &str = "abc123";
+
+fn setup() -> (MockServer, Client, SubmitLog, TempDir) {
+    let server = MockServer::start(SESSION).unwrap();
+    server.add_answer(2021, 1, 1, "7");
+    server.add_answer(2021, 1, 2, "5");
+
+    let dir = TempDir::new().unwrap();
+    let config = Config {
+        url: server.url(),
+        session: Some(SESSION.to_string()),
+        cache: dir.path().to_owned(),
+        answers: dir.path().join("answers.toml"),
+    };
+
+    let client = Client::new(&config);
+    let log = SubmitLog::load(dir.path().join("submissions.toml")).unwrap();
+    (server, client, log, dir)
+}
+
+#[test]
+fn correct_answer() {
+    let (server, client, mut log, _dir) = setup();
+
+    let response = submit(&client, &mut log, 2021, 1, 1, "7").unwrap();
+    assert!(matches!(
+        response,
+        Response::Checked {
+            outcome: Outcome::Correct,
+            wait: None
+        }
+    ));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2021/day/1/answer");
+    assert_eq!(requests[0].body, "level=1&answer=7");
+
+    // solved parts are never submitted again, and the log survives a reload
+    let mut log = SubmitLog::load(log.path()).unwrap();
+    let error = submit(&client, &mut log, 2021, 1, 1, "7").unwrap_err();
+    assert!(matches!(error, SubmitError::AlreadySolved(answer) if answer == "7"));
+    assert_eq!(server.requests().len(), 1);
+
+    let response = submit(&client, &mut log, 2021, 1, 2, "5").unwrap();
+    assert!(matches!(
+        response,
+        Response::Checked {
+            outcome: Outcome::Correct,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn wrong_answer() {
+    let (server, client, mut log, _dir) = setup();
+
+    let response = submit(&client, &mut log, 2021, 1, 1, "9").unwrap();
+    assert_eq!(
+        response,
+        Response::Checked {
+            outcome: Outcome::TooHigh,
+            wait: Some(Duration::from_secs(60)),
+        }
+    );
+
+    // the same answer is never submitted twice, and the cooldown is honoured locally
+    let error = submit(&client, &mut log, 2021, 1, 1, "9").unwrap_err();
+    assert!(matches!(error, SubmitError::AlreadyTried(Outcome::TooHigh)));
+    let error = submit(&client, &mut log, 2021, 1, 1, "6").unwrap_err();
+    assert!(matches!(error, SubmitError::Cooldown(_)));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn cooldown_from_server() {
+    let (server, client, mut log, dir) = setup();
+    submit(&client, &mut log, 2021, 1, 1, "1").unwrap();
+
+    // a fresh log doesn't know about the cooldown, but the server does
+    let mut log = SubmitLog::load(dir.path().join("other.toml")).unwrap();
+    let response = submit(&client, &mut log, 2021, 1, 1, "6").unwrap();
+    assert!(matches!(response, Response::TooSoon(wait) if wait.as_secs() > 0));
+    assert!(matches!(
+        submit(&client, &mut log, 2021, 1, 1, "6"),
+        Err(SubmitError::Cooldown(_))
+    ));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn wrong_level() {
+    let (_server, client, mut log, _dir) = setup();
+
+    let response = submit(&client, &mut log, 2021, 1, 2, "5").unwrap();
+    assert_eq!(response, Response::WrongLevel);
+}